@@ -0,0 +1,186 @@
+use std::str::FromStr;
+
+use anyhow::bail;
+
+use crate::{
+    cipher::{Cipher, Round},
+    GameResult, Hand,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpretation {
+    Hands,
+    Outcomes,
+}
+
+impl Interpretation {
+    fn cipher(&self) -> Cipher {
+        match self {
+            Interpretation::Hands => Cipher::Hands([Hand::Rock, Hand::Paper, Hand::Scissor]),
+            Interpretation::Outcomes => {
+                Cipher::Outcomes([GameResult::Lose, GameResult::Draw, GameResult::Win])
+            }
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Interpretation::Hands => "part_01",
+            Interpretation::Outcomes => "part_02",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "csv" => Format::Csv,
+            "json" => Format::Json,
+            _ => bail!("Unknown format {:?}, expected csv or json", s),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub interpretation: Interpretation,
+    pub round: usize,
+    pub opponent: Hand,
+    pub yours: Hand,
+    pub outcome: GameResult,
+    pub hand_points: usize,
+    pub outcome_points: usize,
+    pub running_total: usize,
+}
+
+pub fn breakdown(rounds: &[Round], interpretation: Interpretation) -> Vec<Row> {
+    let cipher = interpretation.cipher();
+    let mut running_total = 0;
+
+    rounds
+        .iter()
+        .enumerate()
+        .map(|(index, round)| {
+            let (yours, outcome) = cipher.play(round);
+
+            running_total += yours.value() + outcome.value();
+
+            Row {
+                interpretation,
+                round: index + 1,
+                opponent: round.opponent,
+                yours,
+                outcome,
+                hand_points: yours.value(),
+                outcome_points: outcome.value(),
+                running_total,
+            }
+        })
+        .collect()
+}
+
+pub fn export(rows: &[Row], format: Format) -> String {
+    match format {
+        Format::Csv => {
+            let mut lines = vec![
+                "interpretation,round,opponent,yours,outcome,hand_points,outcome_points,running_total"
+                    .to_string(),
+            ];
+
+            lines.extend(rows.iter().map(|row| {
+                format!(
+                    "{},{},{:?},{:?},{:?},{},{},{}",
+                    row.interpretation.name(),
+                    row.round,
+                    row.opponent,
+                    row.yours,
+                    row.outcome,
+                    row.hand_points,
+                    row.outcome_points,
+                    row.running_total
+                )
+            }));
+
+            lines.join("\n")
+        }
+        Format::Json => {
+            let objects: Vec<String> = rows
+                .iter()
+                .map(|row| {
+                    format!(
+                        "  {{\"interpretation\": \"{}\", \"round\": {}, \"opponent\": \"{:?}\", \"yours\": \"{:?}\", \"outcome\": \"{:?}\", \"hand_points\": {}, \"outcome_points\": {}, \"running_total\": {}}}",
+                        row.interpretation.name(),
+                        row.round,
+                        row.opponent,
+                        row.yours,
+                        row.outcome,
+                        row.hand_points,
+                        row.outcome_points,
+                        row.running_total
+                    )
+                })
+                .collect();
+
+            if objects.is_empty() {
+                return "[]".to_string();
+            }
+
+            format!("[\n{}\n]", objects.join(",\n"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cipher::get_rounds, part_01, part_02, read_file};
+
+    #[test]
+    fn example_breakdown() {
+        let input = read_file("example.txt").unwrap();
+        let rounds = get_rounds(&input).unwrap();
+
+        let hands = breakdown(&rounds, Interpretation::Hands);
+        let outcomes = breakdown(&rounds, Interpretation::Outcomes);
+
+        assert_eq!(hands.last().unwrap().running_total, part_01(&input));
+        assert_eq!(outcomes.last().unwrap().running_total, part_02(input));
+
+        assert_eq!(
+            hands[0],
+            Row {
+                interpretation: Interpretation::Hands,
+                round: 1,
+                opponent: Hand::Rock,
+                yours: Hand::Paper,
+                outcome: GameResult::Win,
+                hand_points: 2,
+                outcome_points: 6,
+                running_total: 8,
+            }
+        );
+    }
+
+    #[test]
+    fn example_export() {
+        let input = read_file("example.txt").unwrap();
+        let rounds = get_rounds(&input).unwrap();
+        let rows = breakdown(&rounds, Interpretation::Outcomes);
+
+        let csv = export(&rows, Format::Csv);
+        let json = export(&rows, Format::Json);
+
+        assert_eq!(csv.lines().count(), 4);
+        assert_eq!(csv.lines().nth(1), Some("part_02,1,Rock,Rock,Draw,1,3,4"));
+        assert!(json.starts_with("[\n  {\"interpretation\": \"part_02\", \"round\": 1,"));
+        assert!(json.ends_with("\"running_total\": 12}\n]"));
+    }
+}
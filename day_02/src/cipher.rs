@@ -0,0 +1,215 @@
+use std::fmt;
+
+use anyhow::{anyhow, bail};
+
+use crate::{get_game_result, get_game_result_2, GameResult, Hand};
+
+const SYMBOLS: [char; 3] = ['X', 'Y', 'Z'];
+
+const PERMUTATIONS: [[usize; 3]; 6] = [
+    [0, 1, 2],
+    [0, 2, 1],
+    [1, 0, 2],
+    [1, 2, 0],
+    [2, 0, 1],
+    [2, 1, 0],
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cipher {
+    Hands([Hand; 3]),
+    Outcomes([GameResult; 3]),
+}
+
+impl Cipher {
+    pub fn all() -> Vec<Cipher> {
+        let hands = [Hand::Rock, Hand::Paper, Hand::Scissor];
+        let results = [GameResult::Lose, GameResult::Draw, GameResult::Win];
+
+        PERMUTATIONS
+            .iter()
+            .map(|p| Cipher::Hands([hands[p[0]], hands[p[1]], hands[p[2]]]))
+            .chain(
+                PERMUTATIONS
+                    .iter()
+                    .map(|p| Cipher::Outcomes([results[p[0]], results[p[1]], results[p[2]]])),
+            )
+            .collect()
+    }
+
+    pub fn play(&self, round: &Round) -> (Hand, GameResult) {
+        match self {
+            Cipher::Hands(hands) => {
+                let yours = hands[round.symbol];
+
+                (yours, get_game_result(&yours, &round.opponent))
+            }
+            Cipher::Outcomes(results) => {
+                let result = results[round.symbol];
+
+                (get_game_result_2(&result, &round.opponent), result)
+            }
+        }
+    }
+
+    pub fn score(&self, rounds: &[Round]) -> usize {
+        rounds
+            .iter()
+            .map(|round| {
+                let (yours, result) = self.play(round);
+
+                yours.value() + result.value()
+            })
+            .sum()
+    }
+}
+
+impl fmt::Display for Cipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let meanings: Vec<String> = match self {
+            Cipher::Hands(hands) => hands.iter().map(|h| format!("{:?}", h)).collect(),
+            Cipher::Outcomes(results) => results.iter().map(|r| format!("{:?}", r)).collect(),
+        };
+
+        let mapping: Vec<String> = SYMBOLS
+            .iter()
+            .zip(meanings)
+            .map(|(symbol, meaning)| format!("{}={}", symbol, meaning))
+            .collect();
+
+        write!(f, "{}", mapping.join(" "))
+    }
+}
+
+#[derive(Debug)]
+pub struct Round {
+    pub opponent: Hand,
+    pub symbol: usize,
+}
+
+pub fn get_rounds(input: &[String]) -> anyhow::Result<Vec<Round>> {
+    input
+        .iter()
+        .enumerate()
+        .map(|(index, game)| {
+            let (opponent, symbol) = game
+                .split_once(' ')
+                .ok_or_else(|| anyhow!("Line {}: expected two columns", index + 1))?;
+
+            let opponent = match opponent {
+                "A" | "B" | "C" => opponent.parse::<Hand>()?,
+                _ => bail!("Line {}: unknown opponent hand {:?}", index + 1, opponent),
+            };
+
+            let symbol = SYMBOLS
+                .iter()
+                .position(|s| symbol.len() == 1 && symbol.starts_with(*s))
+                .ok_or_else(|| anyhow!("Line {}: unknown symbol {:?}", index + 1, symbol))?;
+
+            Ok(Round { opponent, symbol })
+        })
+        .collect()
+}
+
+pub fn get_outcomes(s: &str) -> anyhow::Result<Vec<GameResult>> {
+    s.chars()
+        .map(|c| {
+            Ok(match c {
+                'L' => GameResult::Lose,
+                'D' => GameResult::Draw,
+                'W' => GameResult::Win,
+                _ => bail!("Unknown outcome {:?}, expected L, D or W", c),
+            })
+        })
+        .collect()
+}
+
+pub fn analyse(rounds: &[Round]) -> Vec<(Cipher, usize)> {
+    Cipher::all()
+        .into_iter()
+        .map(|cipher| (cipher, cipher.score(rounds)))
+        .collect()
+}
+
+pub fn consistent_with_score(rounds: &[Round], score: usize) -> Vec<Cipher> {
+    analyse(rounds)
+        .into_iter()
+        .filter_map(|(cipher, total)| (total == score).then_some(cipher))
+        .collect()
+}
+
+pub fn consistent_with_outcomes(
+    rounds: &[Round],
+    outcomes: &[GameResult],
+) -> anyhow::Result<Vec<Cipher>> {
+    if rounds.len() != outcomes.len() {
+        bail!("Expected {} outcomes, got {}", rounds.len(), outcomes.len());
+    }
+
+    Ok(Cipher::all()
+        .into_iter()
+        .filter(|cipher| {
+            rounds
+                .iter()
+                .zip(outcomes)
+                .all(|(round, outcome)| cipher.play(round).1 == *outcome)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_file;
+
+    #[test]
+    fn example_analyse() {
+        let input = read_file("example.txt").unwrap();
+        let rounds = get_rounds(&input).unwrap();
+
+        let scores = analyse(&rounds);
+
+        assert_eq!(scores.len(), 12);
+        assert!(scores.contains(&(Cipher::Hands([Hand::Rock, Hand::Paper, Hand::Scissor]), 15)));
+        assert!(scores.contains(&(
+            Cipher::Outcomes([GameResult::Lose, GameResult::Draw, GameResult::Win]),
+            12
+        )));
+    }
+
+    #[test]
+    fn example_consistent_with_score() {
+        let input = read_file("example.txt").unwrap();
+        let rounds = get_rounds(&input).unwrap();
+
+        let ciphers = consistent_with_score(&rounds, 12);
+
+        assert!(ciphers.contains(&Cipher::Outcomes([
+            GameResult::Lose,
+            GameResult::Draw,
+            GameResult::Win
+        ])));
+        assert!(ciphers.iter().all(|c| c.score(&rounds) == 12));
+    }
+
+    #[test]
+    fn example_consistent_with_outcomes() {
+        let input = read_file("example.txt").unwrap();
+        let rounds = get_rounds(&input).unwrap();
+
+        let outcomes = get_outcomes("DLW").unwrap();
+        let ciphers = consistent_with_outcomes(&rounds, &outcomes).unwrap();
+
+        assert!(ciphers.contains(&Cipher::Outcomes([
+            GameResult::Lose,
+            GameResult::Draw,
+            GameResult::Win
+        ])));
+        assert!(ciphers.iter().all(|c| rounds
+            .iter()
+            .zip(&outcomes)
+            .all(|(round, outcome)| c.play(round).1 == *outcome)));
+
+        assert!(consistent_with_outcomes(&rounds, &outcomes[..2]).is_err());
+    }
+}
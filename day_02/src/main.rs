@@ -1,10 +1,113 @@
-use std::{fs, str::FromStr};
+use std::{env, fs, str::FromStr};
+
+mod breakdown;
+mod cipher;
+mod tournament;
 
 fn main() -> anyhow::Result<()> {
     let input = read_file("input.txt")?;
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        Some("cipher") => run_cipher(&input, &args[1..])?,
+        Some("tournament") => run_tournament(&input, &args[1..])?,
+        Some("breakdown") => run_breakdown(&input, &args[1..])?,
+        _ => {
+            println!("Answer 1: {:?}", part_01(&input));
+            println!("Answer 2: {:?}", part_02(input));
+        }
+    }
+
+    Ok(())
+}
+
+fn run_cipher(input: &[String], args: &[String]) -> anyhow::Result<()> {
+    let rounds = cipher::get_rounds(input)?;
+
+    for (cipher, score) in cipher::analyse(&rounds) {
+        println!("{}: {}", cipher, score);
+    }
+
+    match args {
+        [kind, value] if kind == "score" => {
+            for cipher in cipher::consistent_with_score(&rounds, value.parse()?) {
+                println!("Consistent: {}", cipher);
+            }
+        }
+        [kind, value] if kind == "outcomes" => {
+            let outcomes = cipher::get_outcomes(value)?;
+
+            for cipher in cipher::consistent_with_outcomes(&rounds, &outcomes)? {
+                println!("Consistent: {}", cipher);
+            }
+        }
+        [] => {}
+        _ => anyhow::bail!("Usage: cipher [score <total> | outcomes <LDW...>]"),
+    }
+
+    Ok(())
+}
+
+fn run_tournament(input: &[String], args: &[String]) -> anyhow::Result<()> {
+    let rounds = match args.first() {
+        Some(rounds) => rounds.parse()?,
+        None => 1000,
+    };
+
+    let strategies = match args.get(1..) {
+        Some(names) if !names.is_empty() => names
+            .iter()
+            .map(|name| name.parse())
+            .collect::<anyhow::Result<Vec<tournament::Strategy>>>()?,
+        _ => ["rock", "random:2022", "frequency", "markov"]
+            .iter()
+            .map(|name| name.parse())
+            .collect::<anyhow::Result<Vec<tournament::Strategy>>>()?,
+    };
+
+    let guide: Vec<Hand> = cipher::get_rounds(input)?
+        .iter()
+        .map(|round| round.opponent)
+        .collect();
+
+    println!("Round robin ({} rounds per match):", rounds);
+    for standing in tournament::round_robin(&strategies, rounds) {
+        println!(
+            "{}: win rate {:.3}, score {}",
+            standing.name,
+            standing.win_rate(),
+            standing.score
+        );
+    }
+
+    println!("Against guide ({} rounds):", guide.len());
+    for standing in tournament::against_guide(&strategies, &guide, guide.len()) {
+        println!(
+            "{}: win rate {:.3}, score {}",
+            standing.name,
+            standing.win_rate(),
+            standing.score
+        );
+    }
+
+    Ok(())
+}
+
+fn run_breakdown(input: &[String], args: &[String]) -> anyhow::Result<()> {
+    let format = match args.first() {
+        Some(format) => format.parse()?,
+        None => breakdown::Format::Csv,
+    };
+
+    let rounds = cipher::get_rounds(input)?;
+
+    let mut rows = breakdown::breakdown(&rounds, breakdown::Interpretation::Hands);
+    rows.extend(breakdown::breakdown(
+        &rounds,
+        breakdown::Interpretation::Outcomes,
+    ));
 
-    println!("Answer 1: {:?}", part_01(&input));
-    println!("Answer 2: {:?}", part_02(input));
+    println!("{}", breakdown::export(&rows, format));
 
     Ok(())
 }
@@ -15,7 +118,7 @@ fn read_file(filename: &str) -> std::io::Result<Vec<String>> {
     Ok(input.lines().filter_map(|line| line.parse().ok()).collect())
 }
 
-fn part_01(input: &Vec<String>) -> usize {
+fn part_01(input: &[String]) -> usize {
     input
         .iter()
         .map(|game| {
@@ -47,7 +150,7 @@ fn part_02(input: Vec<String>) -> usize {
         .sum()
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Hand {
     Rock,
     Paper,
@@ -80,7 +183,7 @@ impl FromStr for Hand {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum GameResult {
     Win,
     Draw,
@@ -0,0 +1,276 @@
+use std::{fmt, str::FromStr};
+
+use anyhow::bail;
+
+use crate::{get_game_result, get_game_result_2, GameResult, Hand};
+
+const HANDS: [Hand; 3] = [Hand::Rock, Hand::Paper, Hand::Scissor];
+
+fn hand_index(hand: &Hand) -> usize {
+    match hand {
+        Hand::Rock => 0,
+        Hand::Paper => 1,
+        Hand::Scissor => 2,
+    }
+}
+
+fn predict(counts: &[usize; 3]) -> Option<Hand> {
+    let (index, count) = counts
+        .iter()
+        .enumerate()
+        .max_by(|(a, x), (b, y)| x.cmp(y).then(b.cmp(a)))?;
+
+    (*count > 0).then_some(HANDS[index])
+}
+
+fn counter(predicted: Option<Hand>) -> Hand {
+    get_game_result_2(&GameResult::Win, &predicted.unwrap_or(Hand::Rock))
+}
+
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed.max(1))
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Strategy {
+    Fixed(Hand),
+    Random(u64, Rng),
+    Frequency([usize; 3]),
+    Markov(Option<Hand>, [[usize; 3]; 3]),
+    Replay(Vec<Hand>, usize),
+}
+
+impl Strategy {
+    pub fn choose(&mut self) -> Hand {
+        match self {
+            Strategy::Fixed(hand) => *hand,
+            Strategy::Random(_, rng) => HANDS[(rng.next() % 3) as usize],
+            Strategy::Frequency(counts) => counter(predict(counts)),
+            Strategy::Markov(last, transitions) => {
+                counter(last.and_then(|last| predict(&transitions[hand_index(&last)])))
+            }
+            Strategy::Replay(hands, index) => {
+                let hand = hands[*index % hands.len()];
+                *index += 1;
+                hand
+            }
+        }
+    }
+
+    pub fn observe(&mut self, opponent: &Hand) {
+        match self {
+            Strategy::Frequency(counts) => counts[hand_index(opponent)] += 1,
+            Strategy::Markov(last, transitions) => {
+                if let Some(last) = last {
+                    transitions[hand_index(last)][hand_index(opponent)] += 1;
+                }
+
+                *last = Some(*opponent);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Strategy::Fixed(Hand::Rock) => write!(f, "rock"),
+            Strategy::Fixed(Hand::Paper) => write!(f, "paper"),
+            Strategy::Fixed(Hand::Scissor) => write!(f, "scissor"),
+            Strategy::Random(seed, _) => write!(f, "random:{}", seed),
+            Strategy::Frequency(_) => write!(f, "frequency"),
+            Strategy::Markov(_, _) => write!(f, "markov"),
+            Strategy::Replay(_, _) => write!(f, "guide"),
+        }
+    }
+}
+
+impl FromStr for Strategy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.split_once(':') {
+            Some(("random", seed)) => {
+                let seed = seed.parse()?;
+
+                Strategy::Random(seed, Rng::new(seed))
+            }
+            None => match s {
+                "rock" => Strategy::Fixed(Hand::Rock),
+                "paper" => Strategy::Fixed(Hand::Paper),
+                "scissor" => Strategy::Fixed(Hand::Scissor),
+                "frequency" => Strategy::Frequency([0; 3]),
+                "markov" => Strategy::Markov(None, [[0; 3]; 3]),
+                _ => bail!("Unknown strategy {:?}", s),
+            },
+            _ => bail!("Unknown strategy {:?}", s),
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub name: String,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    pub score: usize,
+}
+
+impl Standing {
+    fn record(&mut self, yours: &Hand, result: &GameResult) {
+        match result {
+            GameResult::Win => self.wins += 1,
+            GameResult::Draw => self.draws += 1,
+            GameResult::Lose => self.losses += 1,
+        }
+
+        self.score += yours.value() + result.value();
+    }
+
+    fn merge(&mut self, other: &Standing) {
+        self.wins += other.wins;
+        self.draws += other.draws;
+        self.losses += other.losses;
+        self.score += other.score;
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        let games = self.wins + self.draws + self.losses;
+
+        if games == 0 {
+            return 0.0;
+        }
+
+        self.wins as f64 / games as f64
+    }
+}
+
+pub fn play_match(a: &Strategy, b: &Strategy, rounds: usize) -> (Standing, Standing) {
+    let (mut a, mut b) = (a.clone(), b.clone());
+    let mut standing_a = Standing {
+        name: a.to_string(),
+        ..Default::default()
+    };
+    let mut standing_b = Standing {
+        name: b.to_string(),
+        ..Default::default()
+    };
+
+    for _ in 0..rounds {
+        let hand_a = a.choose();
+        let hand_b = b.choose();
+
+        standing_a.record(&hand_a, &get_game_result(&hand_a, &hand_b));
+        standing_b.record(&hand_b, &get_game_result(&hand_b, &hand_a));
+
+        a.observe(&hand_b);
+        b.observe(&hand_a);
+    }
+
+    (standing_a, standing_b)
+}
+
+pub fn round_robin(strategies: &[Strategy], rounds: usize) -> Vec<Standing> {
+    let mut standings: Vec<Standing> = strategies
+        .iter()
+        .map(|strategy| Standing {
+            name: strategy.to_string(),
+            ..Default::default()
+        })
+        .collect();
+
+    for i in 0..strategies.len() {
+        for j in i + 1..strategies.len() {
+            let (a, b) = play_match(&strategies[i], &strategies[j], rounds);
+
+            standings[i].merge(&a);
+            standings[j].merge(&b);
+        }
+    }
+
+    standings
+}
+
+pub fn against_guide(strategies: &[Strategy], guide: &[Hand], rounds: usize) -> Vec<Standing> {
+    let opponent = Strategy::Replay(guide.to_vec(), 0);
+    let rounds = if guide.is_empty() { 0 } else { rounds };
+
+    strategies
+        .iter()
+        .map(|strategy| play_match(strategy, &opponent, rounds).0)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cipher::get_rounds, read_file};
+
+    #[test]
+    fn test_fixed_match() {
+        let (paper, rock) = play_match(
+            &Strategy::Fixed(Hand::Paper),
+            &Strategy::Fixed(Hand::Rock),
+            10,
+        );
+
+        assert_eq!(paper.wins, 10);
+        assert_eq!(paper.score, 10 * (2 + 6));
+        assert_eq!(rock.losses, 10);
+        assert_eq!(rock.score, 10);
+        assert_eq!(paper.win_rate(), 1.0);
+    }
+
+    #[test]
+    fn test_predictors_beat_fixed() {
+        let scissor = Strategy::Fixed(Hand::Scissor);
+
+        let (frequency, _) = play_match(&"frequency".parse().unwrap(), &scissor, 100);
+        let (markov, _) = play_match(&"markov".parse().unwrap(), &scissor, 100);
+
+        assert_eq!(frequency.wins, 99);
+        assert_eq!(markov.wins, 98);
+    }
+
+    #[test]
+    fn test_random_is_seeded() {
+        let random: Strategy = "random:42".parse().unwrap();
+        let rock = Strategy::Fixed(Hand::Rock);
+
+        assert_eq!(
+            play_match(&random, &rock, 50),
+            play_match(&random, &rock, 50)
+        );
+    }
+
+    #[test]
+    fn example_against_guide() {
+        let input = read_file("example.txt").unwrap();
+        let guide: Vec<Hand> = get_rounds(&input)
+            .unwrap()
+            .iter()
+            .map(|round| round.opponent)
+            .collect();
+
+        let standings = against_guide(&[Strategy::Fixed(Hand::Paper)], &guide, 3);
+
+        assert_eq!(standings[0].wins, 1);
+        assert_eq!(standings[0].draws, 1);
+        assert_eq!(standings[0].losses, 1);
+        assert_eq!(standings[0].score, 3 * 2 + 6 + 3);
+    }
+}
@@ -0,0 +1,141 @@
+use anyhow::bail;
+
+use crate::{item_set::ItemSet, validation};
+
+struct Search {
+    sets: Vec<ItemSet>,
+    group_size: usize,
+    used: Vec<bool>,
+    groups: Vec<Vec<usize>>,
+}
+
+impl Search {
+    fn candidates(&self, first: usize) -> Vec<Vec<usize>> {
+        let mut candidates = vec![];
+
+        self.extend(
+            &mut vec![first],
+            self.sets[first],
+            first + 1,
+            &mut candidates,
+        );
+
+        candidates
+    }
+
+    fn extend(
+        &self,
+        group: &mut Vec<usize>,
+        shared: ItemSet,
+        start: usize,
+        candidates: &mut Vec<Vec<usize>>,
+    ) {
+        if group.len() == self.group_size {
+            if shared.len() == 1 {
+                candidates.push(group.clone());
+            }
+
+            return;
+        }
+
+        for next in start..self.sets.len() {
+            if self.used[next] {
+                continue;
+            }
+
+            let shared = shared.intersection(self.sets[next]);
+
+            if shared.is_empty() {
+                continue;
+            }
+
+            group.push(next);
+            self.extend(group, shared, next + 1, candidates);
+            group.pop();
+        }
+    }
+
+    fn solve(&mut self) -> bool {
+        let first = match self.used.iter().position(|used| !used) {
+            Some(first) => first,
+            None => return true,
+        };
+
+        for group in self.candidates(first) {
+            group.iter().for_each(|&index| self.used[index] = true);
+            self.groups.push(group);
+
+            if self.solve() {
+                return true;
+            }
+
+            let group = self.groups.pop().unwrap();
+            group.iter().for_each(|&index| self.used[index] = false);
+        }
+
+        false
+    }
+}
+
+pub fn discover_groups(
+    rucksacks: &[String],
+    group_size: usize,
+) -> anyhow::Result<Option<Vec<Vec<usize>>>> {
+    if group_size == 0 {
+        bail!("Group size must be at least 1");
+    }
+
+    validation::check(validation::validate_items(rucksacks))?;
+
+    if !rucksacks.len().is_multiple_of(group_size) {
+        bail!(
+            "{} elves do not divide into groups of {}",
+            rucksacks.len(),
+            group_size
+        );
+    }
+
+    let mut search = Search {
+        sets: rucksacks
+            .iter()
+            .map(|rucksack| rucksack.chars().collect())
+            .collect(),
+        group_size,
+        used: vec![false; rucksacks.len()],
+        groups: vec![],
+    };
+
+    Ok(search.solve().then_some(search.groups))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{get_badges, read_file};
+
+    #[test]
+    fn example_discover_groups() {
+        let mut input = read_file("example.txt").unwrap();
+        input.swap(1, 4);
+
+        let groups = discover_groups(&input, 3).unwrap().unwrap();
+
+        assert_eq!(groups.len(), 2);
+        assert!(groups.iter().all(|group| {
+            let group: Vec<String> = group.iter().map(|&i| input[i].clone()).collect();
+
+            get_badges(&group).len() == 1
+        }));
+    }
+
+    #[test]
+    fn test_no_grouping_exists() {
+        let input: Vec<String> = ["ab", "ab", "ab", "cd", "ef", "gh"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        assert_eq!(discover_groups(&input, 3).unwrap(), None);
+        assert!(discover_groups(&input[..5], 3).is_err());
+    }
+}
@@ -0,0 +1,67 @@
+use crate::calc_item_priority;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub fn from_item(item: &char) -> Self {
+        ItemSet(1 << calc_item_priority(item))
+    }
+
+    pub fn union(self, other: ItemSet) -> Self {
+        ItemSet(self.0 | other.0)
+    }
+
+    pub fn intersection(self, other: ItemSet) -> Self {
+        ItemSet(self.0 & other.0)
+    }
+
+    pub fn len(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn priority_sum(self) -> u32 {
+        let mut bits = self.0;
+        let mut sum = 0;
+
+        while bits != 0 {
+            sum += bits.trailing_zeros();
+            bits &= bits - 1;
+        }
+
+        sum
+    }
+}
+
+impl FromIterator<char> for ItemSet {
+    fn from_iter<T: IntoIterator<Item = char>>(iter: T) -> Self {
+        iter.into_iter().fold(ItemSet::default(), |set, item| {
+            set.union(ItemSet::from_item(&item))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_operations() {
+        let a: ItemSet = "vJrwpWtwJgWr".chars().collect();
+        let b: ItemSet = "hcsFMMfFFhFp".chars().collect();
+
+        assert_eq!(a.intersection(b), ItemSet::from_item(&'p'));
+        assert_eq!(a.intersection(b).priority_sum(), 16);
+        assert_eq!(
+            ItemSet::from_item(&'a')
+                .union(ItemSet::from_item(&'Z'))
+                .priority_sum(),
+            1 + 52
+        );
+        assert_eq!(ItemSet::default().priority_sum(), 0);
+    }
+}
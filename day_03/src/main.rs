@@ -1,10 +1,54 @@
-use std::{collections::HashSet, fs};
+use std::{env, fs};
+
+use anyhow::bail;
+
+use item_set::ItemSet;
+
+mod discovery;
+mod item_set;
+mod validation;
 
 fn main() -> anyhow::Result<()> {
     let input = read_file("input.txt")?;
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.iter().map(String::as_str).collect::<Vec<&str>>()[..] {
+        [] => {
+            println!("Answer 1: {}", part_01(&input)?);
+            println!("Answer 2: {}", part_02(&input)?);
+        }
+        ["discover", group_size] => run_discovery(&input, group_size.parse()?)?,
+        [compartments, group_size] => {
+            println!(
+                "Answer 1: {}",
+                sum_shared_items(&input, compartments.parse()?)?
+            );
+            println!("Answer 2: {}", sum_badges(&input, group_size.parse()?)?);
+        }
+        _ => bail!("Usage: day_03 [<compartments> <group size> | discover <group size>]"),
+    }
+
+    Ok(())
+}
 
-    println!("Answer 1: {:?}", part_01(&input));
-    println!("Answer 2: {:?}", part_02(&input));
+fn run_discovery(rucksacks: &[String], group_size: usize) -> anyhow::Result<()> {
+    match discovery::discover_groups(rucksacks, group_size)? {
+        Some(groups) => {
+            let mut total = 0;
+
+            for group in groups {
+                let lines: Vec<String> = group.iter().map(|i| (i + 1).to_string()).collect();
+                let group: Vec<String> = group.iter().map(|&i| rucksacks[i].clone()).collect();
+                let priority = get_badges(&group).priority_sum();
+
+                total += priority;
+                println!("Lines {}: badge priority {}", lines.join(", "), priority);
+            }
+
+            println!("Total: {}", total);
+        }
+        None => println!("No grouping exists"),
+    }
 
     Ok(())
 }
@@ -15,44 +59,79 @@ fn read_file(filename: &str) -> std::io::Result<Vec<String>> {
     Ok(input.lines().filter_map(|line| line.parse().ok()).collect())
 }
 
-fn part_01(rucksacks: &Vec<String>) -> u32 {
-    rucksacks
+fn part_01(rucksacks: &[String]) -> anyhow::Result<u32> {
+    sum_shared_items(rucksacks, 2)
+}
+
+fn part_02(rucksacks: &[String]) -> anyhow::Result<u32> {
+    sum_badges(rucksacks, 3)
+}
+
+fn get_compartments(rucksack: &str, compartments: usize) -> Vec<ItemSet> {
+    let size = rucksack.len() / compartments;
+
+    if size == 0 {
+        return vec![ItemSet::default(); compartments];
+    }
+
+    rucksack
+        .as_bytes()
+        .chunks(size)
+        .map(|compartment| compartment.iter().map(|item| *item as char).collect())
+        .collect()
+}
+
+fn get_shared_items(rucksack: &str, compartments: usize) -> ItemSet {
+    get_compartments(rucksack, compartments)
+        .into_iter()
+        .reduce(ItemSet::intersection)
+        .unwrap_or_default()
+}
+
+fn get_badges(group: &[String]) -> ItemSet {
+    group
         .iter()
-        .map(|rucksack| {
-            let (compartment_1, compartment_2) = rucksack.split_at(rucksack.len() / 2);
+        .map(|rucksack| rucksack.chars().collect::<ItemSet>())
+        .reduce(ItemSet::intersection)
+        .unwrap_or_default()
+}
 
-            let set: HashSet<char> = compartment_1.chars().collect();
+fn sum_shared_items(rucksacks: &[String], compartments: usize) -> anyhow::Result<u32> {
+    if compartments == 0 {
+        bail!("Compartment count must be at least 1");
+    }
 
-            let char_found = compartment_2.chars().find(|char| set.contains(char));
+    validation::check(validation::validate_rucksacks(rucksacks, compartments))?;
 
-            match char_found {
-                Some(char) => calc_item_priority(&char),
-                None => 0,
-            }
-        })
+    Ok(score_shared_items(rucksacks, compartments))
+}
+
+fn score_shared_items(rucksacks: &[String], compartments: usize) -> u32 {
+    rucksacks
+        .iter()
+        .map(|rucksack| get_shared_items(rucksack, compartments).priority_sum())
         .sum()
 }
 
-fn part_02(rucksacks: &Vec<String>) -> u32 {
+fn sum_badges(rucksacks: &[String], group_size: usize) -> anyhow::Result<u32> {
+    if group_size == 0 {
+        bail!("Group size must be at least 1");
+    }
+
+    validation::check(validation::validate_groups(rucksacks, group_size))?;
+
+    Ok(score_badges(rucksacks, group_size))
+}
+
+fn score_badges(rucksacks: &[String], group_size: usize) -> u32 {
     rucksacks
-        .chunks(3)
-        .map(|group| {
-            group
-                .iter()
-                .map(|rucksack| rucksack.chars().collect::<HashSet<char>>())
-                .reduce(|acc, rucksack_content| {
-                    acc.intersection(&rucksack_content).copied().collect()
-                })
-                .unwrap()
-                .iter()
-                .map(|char| calc_item_priority(char))
-                .sum::<u32>()
-        })
+        .chunks(group_size)
+        .map(|group| get_badges(group).priority_sum())
         .sum()
 }
 
 fn calc_item_priority(char: &char) -> u32 {
-    let ascii = char.clone() as u32;
+    let ascii = *char as u32;
 
     if char.is_lowercase() {
         return ascii - 96;
@@ -64,6 +143,7 @@ fn calc_item_priority(char: &char) -> u32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::{collections::HashSet, time::Instant};
 
     #[test]
     fn test_calc_score() {
@@ -79,13 +159,124 @@ mod tests {
     fn example_01() {
         let input = read_file("example.txt").unwrap();
 
-        assert_eq!(part_01(&input), 157);
+        assert_eq!(part_01(&input).unwrap(), 157);
     }
 
     #[test]
     fn example_02() {
         let input = read_file("example.txt").unwrap();
 
-        assert_eq!(part_02(&input), 70);
+        assert_eq!(part_02(&input).unwrap(), 70);
+    }
+
+    #[test]
+    fn test_configurable_sizes() {
+        let input: Vec<String> = ["abacadaa", "aXYZ", "aBCD", "bxya", "Azza", "zaqq"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        assert_eq!(sum_shared_items(&input[..1], 4).unwrap(), 1);
+        assert_eq!(sum_badges(&input[1..3], 2).unwrap(), 1);
+        assert_eq!(sum_badges(&input[1..], 5).unwrap(), 1);
+
+        let error = sum_shared_items(&input[..2], 3).unwrap_err().to_string();
+        assert!(error.contains("Line 1: rucksack length 8 does not divide into 3 compartments"));
+        assert!(error.contains("Line 2: rucksack length 4 does not divide into 3 compartments"));
+
+        let error = sum_badges(&input, 4).unwrap_err().to_string();
+        assert!(error.contains("Line 5: group has 2 elves, expected 4"));
+        assert!(error.contains("6 elves do not divide into groups of 4"));
+
+        assert!(sum_shared_items(&input, 0).is_err());
+        assert!(sum_badges(&input, 0).is_err());
+    }
+
+    fn part_01_hash_set(rucksacks: &[String]) -> u32 {
+        rucksacks
+            .iter()
+            .map(|rucksack| {
+                let (compartment_1, compartment_2) = rucksack.split_at(rucksack.len() / 2);
+
+                let set: HashSet<char> = compartment_1.chars().collect();
+
+                match compartment_2.chars().find(|char| set.contains(char)) {
+                    Some(char) => calc_item_priority(&char),
+                    None => 0,
+                }
+            })
+            .sum()
+    }
+
+    fn part_02_hash_set(rucksacks: &[String]) -> u32 {
+        rucksacks
+            .chunks(3)
+            .map(|group| {
+                group
+                    .iter()
+                    .map(|rucksack| rucksack.chars().collect::<HashSet<char>>())
+                    .reduce(|acc, rucksack_content| {
+                        acc.intersection(&rucksack_content).copied().collect()
+                    })
+                    .unwrap()
+                    .iter()
+                    .map(calc_item_priority)
+                    .sum::<u32>()
+            })
+            .sum()
+    }
+
+    fn generate_rucksacks(count: usize, compartment_size: usize) -> Vec<String> {
+        let items: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+        let mut seed: u64 = 2022;
+        let mut next = move || {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) as usize
+        };
+
+        (0..count)
+            .map(|_| {
+                let shared = items[next() % items.len()];
+                let (left, right): (Vec<char>, Vec<char>) = items
+                    .iter()
+                    .filter(|item| **item != shared)
+                    .partition(|_| next() % 2 == 0);
+
+                let mut compartment_1: String = (1..compartment_size)
+                    .map(|_| left[next() % left.len()])
+                    .collect();
+                let mut compartment_2: String = (1..compartment_size)
+                    .map(|_| right[next() % right.len()])
+                    .collect();
+
+                compartment_1.push(shared);
+                compartment_2.insert(0, shared);
+
+                compartment_1 + &compartment_2
+            })
+            .collect()
+    }
+
+    #[test]
+    #[ignore]
+    fn bench_item_set() {
+        let rucksacks = generate_rucksacks(300_000, 24);
+
+        let start = Instant::now();
+        let expected = (part_01_hash_set(&rucksacks), part_02_hash_set(&rucksacks));
+        let hash_set = start.elapsed();
+
+        let start = Instant::now();
+        let result = (
+            score_shared_items(&rucksacks, 2),
+            score_badges(&rucksacks, 3),
+        );
+        let item_set = start.elapsed();
+
+        println!("HashSet<char>: {:?}, ItemSet: {:?}", hash_set, item_set);
+
+        assert_eq!(result, expected);
     }
 }
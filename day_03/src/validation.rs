@@ -0,0 +1,235 @@
+use std::fmt;
+
+use anyhow::bail;
+
+use crate::{get_badges, get_shared_items};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    UnevenLength {
+        len: usize,
+        compartments: usize,
+    },
+    InvalidItem(char),
+    SharedItems(u32),
+    IncompleteGroup {
+        size: usize,
+        expected: usize,
+        elves: usize,
+    },
+    Badges(u32),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    pub line: usize,
+    pub issue: Issue,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Line {}: ", self.line)?;
+
+        match &self.issue {
+            Issue::UnevenLength { len, compartments } => write!(
+                f,
+                "rucksack length {} does not divide into {} compartments",
+                len, compartments
+            ),
+            Issue::InvalidItem(item) => write!(f, "invalid item {:?}", item),
+            Issue::SharedItems(count) => {
+                write!(f, "compartments share {} item types, expected 1", count)
+            }
+            Issue::IncompleteGroup {
+                size,
+                expected,
+                elves,
+            } => write!(
+                f,
+                "group has {} elves, expected {} ({} elves do not divide into groups of {})",
+                size, expected, elves, expected
+            ),
+            Issue::Badges(count) => write!(f, "group shares {} item types, expected 1", count),
+        }
+    }
+}
+
+fn invalid_items(line: usize, rucksack: &str) -> Vec<Problem> {
+    rucksack
+        .chars()
+        .filter(|item| !item.is_ascii_alphabetic())
+        .map(|item| Problem {
+            line,
+            issue: Issue::InvalidItem(item),
+        })
+        .collect()
+}
+
+pub fn validate_items(rucksacks: &[String]) -> Vec<Problem> {
+    rucksacks
+        .iter()
+        .enumerate()
+        .flat_map(|(index, rucksack)| invalid_items(index + 1, rucksack))
+        .collect()
+}
+
+pub fn validate_rucksacks(rucksacks: &[String], compartments: usize) -> Vec<Problem> {
+    rucksacks
+        .iter()
+        .enumerate()
+        .flat_map(|(index, rucksack)| {
+            let line = index + 1;
+            let mut problems = invalid_items(line, rucksack);
+
+            let len = rucksack.chars().count();
+
+            if len % compartments != 0 {
+                problems.push(Problem {
+                    line,
+                    issue: Issue::UnevenLength { len, compartments },
+                });
+            }
+
+            if problems.is_empty() {
+                let shared = get_shared_items(rucksack, compartments).len();
+
+                if shared != 1 {
+                    problems.push(Problem {
+                        line,
+                        issue: Issue::SharedItems(shared),
+                    });
+                }
+            }
+
+            problems
+        })
+        .collect()
+}
+
+pub fn validate_groups(rucksacks: &[String], group_size: usize) -> Vec<Problem> {
+    rucksacks
+        .chunks(group_size)
+        .enumerate()
+        .flat_map(|(index, group)| {
+            let line = index * group_size + 1;
+            let mut problems: Vec<Problem> = group
+                .iter()
+                .enumerate()
+                .flat_map(|(offset, rucksack)| invalid_items(line + offset, rucksack))
+                .collect();
+
+            if group.len() != group_size {
+                problems.push(Problem {
+                    line,
+                    issue: Issue::IncompleteGroup {
+                        size: group.len(),
+                        expected: group_size,
+                        elves: rucksacks.len(),
+                    },
+                });
+            }
+
+            if problems.is_empty() {
+                let badges = get_badges(group).len();
+
+                if badges != 1 {
+                    problems.push(Problem {
+                        line,
+                        issue: Issue::Badges(badges),
+                    });
+                }
+            }
+
+            problems
+        })
+        .collect()
+}
+
+pub fn check(problems: Vec<Problem>) -> anyhow::Result<()> {
+    if problems.is_empty() {
+        return Ok(());
+    }
+
+    let report: Vec<String> = problems.iter().map(|problem| problem.to_string()).collect();
+
+    bail!("Invalid input:\n{}", report.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read_file;
+
+    #[test]
+    fn example_is_valid() {
+        let input = read_file("example.txt").unwrap();
+
+        assert_eq!(validate_rucksacks(&input, 2), vec![]);
+        assert_eq!(validate_groups(&input, 3), vec![]);
+    }
+
+    #[test]
+    fn test_invalid_rucksacks() {
+        let input: Vec<String> = ["abcA", "abcdea", "aXa1", "abcab", "abab"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        assert_eq!(
+            validate_rucksacks(&input, 2),
+            vec![
+                Problem {
+                    line: 1,
+                    issue: Issue::SharedItems(0)
+                },
+                Problem {
+                    line: 3,
+                    issue: Issue::InvalidItem('1')
+                },
+                Problem {
+                    line: 4,
+                    issue: Issue::UnevenLength {
+                        len: 5,
+                        compartments: 2
+                    }
+                },
+                Problem {
+                    line: 5,
+                    issue: Issue::SharedItems(2)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_groups() {
+        let input: Vec<String> = ["ab", "ac", "ad", "ab", "ab", "ab", "a"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        assert_eq!(
+            validate_groups(&input, 3),
+            vec![
+                Problem {
+                    line: 4,
+                    issue: Issue::Badges(2)
+                },
+                Problem {
+                    line: 7,
+                    issue: Issue::IncompleteGroup {
+                        size: 1,
+                        expected: 3,
+                        elves: 7
+                    }
+                },
+            ]
+        );
+        assert!(check(validate_groups(&input, 3))
+            .unwrap_err()
+            .to_string()
+            .contains(
+                "Line 7: group has 1 elves, expected 3 (7 elves do not divide into groups of 3)"
+            ));
+    }
+}
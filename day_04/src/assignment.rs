@@ -0,0 +1,132 @@
+use std::fmt;
+
+use anyhow::{anyhow, bail, Context};
+
+use crate::interval::Interval;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elf {
+    pub line: usize,
+    pub position: usize,
+    pub range: Interval,
+    pub reversed: bool,
+}
+
+impl fmt::Display for Elf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {} elf {} ({}-{})",
+            self.line, self.position, self.range.start, self.range.end
+        )
+    }
+}
+
+fn split_dash(elf: &str) -> Option<(&str, &str)> {
+    elf.char_indices()
+        .skip(1)
+        .find(|(i, c)| *c == '-' && elf[..*i].ends_with(|c: char| c.is_ascii_digit()))
+        .map(|(i, _)| (&elf[..i], &elf[i + 1..]))
+}
+
+fn parse_bound(bound: &str) -> anyhow::Result<i64> {
+    bound
+        .trim()
+        .parse()
+        .with_context(|| format!("invalid bound {:?}", bound.trim()))
+}
+
+pub fn get_range(elf: &str) -> anyhow::Result<(Interval, bool)> {
+    let elf = elf.trim();
+
+    let (lower, upper, half_open) = if let Some((lower, upper)) = elf.split_once("..=") {
+        (lower, upper, false)
+    } else if let Some((lower, upper)) = elf.split_once("..") {
+        (lower, upper, true)
+    } else if let Some((lower, upper)) = split_dash(elf) {
+        (lower, upper, false)
+    } else {
+        bail!("invalid range {:?}, expected a-b, a..b or a..=b", elf);
+    };
+
+    let lower = parse_bound(lower)?;
+    let mut upper = parse_bound(upper)?;
+    let reversed = lower > upper;
+
+    if half_open {
+        upper = if reversed {
+            upper + 1
+        } else {
+            upper
+                .checked_sub(1)
+                .ok_or_else(|| anyhow!("exclusive end {} is out of range", upper))?
+        };
+    }
+
+    if reversed {
+        return Ok((Interval::new(upper, lower), reversed));
+    }
+
+    Ok((Interval::new(lower, upper), reversed))
+}
+
+pub fn get_elf_list(input: &[String]) -> anyhow::Result<Vec<Elf>> {
+    let mut elves = vec![];
+
+    for (index, group) in input.iter().enumerate() {
+        if group.trim().is_empty() {
+            continue;
+        }
+
+        for (position, elf) in group.split(',').enumerate() {
+            let (range, reversed) = get_range(elf)
+                .map_err(|e| anyhow!("Line {} elf {}: {}", index + 1, position + 1, e))?;
+
+            elves.push(Elf {
+                line: index + 1,
+                position: position + 1,
+                range,
+                reversed,
+            });
+        }
+    }
+
+    Ok(elves)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range_syntax() {
+        assert_eq!(get_range("2-4").unwrap(), (Interval::new(2, 4), false));
+        assert_eq!(get_range("-5--1").unwrap(), (Interval::new(-5, -1), false));
+        assert_eq!(get_range("-5-3").unwrap(), (Interval::new(-5, 3), false));
+        assert_eq!(get_range("2..5").unwrap(), (Interval::new(2, 4), false));
+        assert_eq!(get_range("2..=5").unwrap(), (Interval::new(2, 5), false));
+        assert_eq!(
+            get_range("-3..=-1").unwrap(),
+            (Interval::new(-3, -1), false)
+        );
+        assert_eq!(get_range("8-6").unwrap(), (Interval::new(6, 8), true));
+        assert_eq!(get_range("8..6").unwrap(), (Interval::new(7, 8), true));
+        assert_eq!(get_range("7..6").unwrap(), (Interval::new(7, 7), true));
+        assert!(get_range("3..3").unwrap().0.is_empty());
+        assert!(get_range("-9223372036854775808..-9223372036854775808").is_err());
+
+        assert!(get_range("3").is_err());
+        assert!(get_range("a-3").is_err());
+        assert!(get_range("1-").is_err());
+    }
+
+    #[test]
+    fn test_elf_list_errors() {
+        let input = vec!["1-2,3-4,5-6".to_string(), "1-2,x".to_string()];
+
+        let error = get_elf_list(&input).unwrap_err().to_string();
+
+        assert!(error.starts_with("Line 2 elf 2: invalid range \"x\""));
+        assert_eq!(get_elf_list(&input[..1]).unwrap().len(), 3);
+    }
+}
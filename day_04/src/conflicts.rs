@@ -0,0 +1,182 @@
+use anyhow::anyhow;
+
+use crate::{assignment::Elf, interval::Interval};
+
+#[derive(Debug)]
+pub struct ConflictGraph {
+    pub elves: Vec<Elf>,
+    pub degrees: Vec<usize>,
+    pub components: Vec<Vec<usize>>,
+}
+
+impl ConflictGraph {
+    pub fn new(elves: Vec<Elf>) -> Self {
+        let mut starts: Vec<i64> = elves
+            .iter()
+            .filter(|elf| !elf.range.is_empty())
+            .map(|elf| elf.range.start)
+            .collect();
+        let mut ends: Vec<i64> = elves
+            .iter()
+            .filter(|elf| !elf.range.is_empty())
+            .map(|elf| elf.range.end)
+            .collect();
+
+        starts.sort();
+        ends.sort();
+
+        let degrees = elves
+            .iter()
+            .map(|elf| {
+                if elf.range.is_empty() {
+                    return 0;
+                }
+
+                let started = starts.partition_point(|start| *start <= elf.range.end);
+                let ended = ends.partition_point(|end| *end < elf.range.start);
+
+                started - ended - 1
+            })
+            .collect();
+
+        let (mut order, empty): (Vec<usize>, Vec<usize>) =
+            (0..elves.len()).partition(|&i| !elves[i].range.is_empty());
+        order.sort_by_key(|&i| (elves[i].range.start, elves[i].range.end));
+
+        let mut components: Vec<Vec<usize>> = vec![];
+        let mut reach = i64::MIN;
+
+        for i in order {
+            let range = elves[i].range;
+
+            match components.last_mut() {
+                Some(component) if range.start <= reach => component.push(i),
+                _ => components.push(vec![i]),
+            }
+
+            reach = reach.max(range.end);
+        }
+
+        components.extend(empty.into_iter().map(|i| vec![i]));
+
+        for component in components.iter_mut() {
+            component.sort();
+        }
+
+        ConflictGraph {
+            elves,
+            degrees,
+            components,
+        }
+    }
+
+    pub fn most_conflicted(&self, n: usize) -> Vec<(Elf, usize)> {
+        let mut ranked: Vec<(Elf, usize)> = self
+            .elves
+            .iter()
+            .copied()
+            .zip(self.degrees.iter().copied())
+            .collect();
+
+        ranked.sort_by(|(_, a), (_, b)| b.cmp(a));
+        ranked.truncate(n);
+
+        ranked
+    }
+}
+
+pub fn reassign(elves: &[Elf]) -> anyhow::Result<Vec<Elf>> {
+    let mut order: Vec<usize> = (0..elves.len()).collect();
+    order.sort_by_key(|&i| (elves[i].range.start, elves[i].range.end));
+
+    let mut reassigned = elves.to_vec();
+    let mut last_end: Option<i64> = None;
+
+    for i in order {
+        let range = elves[i].range;
+
+        if range.is_empty() {
+            continue;
+        }
+
+        let overflow = || anyhow!("{}: stacked placement runs past {}", elves[i], i64::MAX);
+
+        let start = match last_end {
+            Some(last_end) => range
+                .start
+                .max(last_end.checked_add(1).ok_or_else(overflow)?),
+            None => range.start,
+        };
+        let end = start
+            .checked_add_unsigned(range.end.abs_diff(range.start))
+            .ok_or_else(overflow)?;
+
+        reassigned[i].range = Interval::new(start, end);
+        last_end = Some(end);
+    }
+
+    Ok(reassigned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assignment::get_elf_list, read_file};
+
+    #[test]
+    fn example_conflict_graph() {
+        let input = read_file("example.txt").unwrap();
+        let graph = ConflictGraph::new(get_elf_list(&input).unwrap());
+
+        assert_eq!(graph.elves.len(), 12);
+        assert_eq!(graph.components.len(), 1);
+        assert_eq!(graph.degrees[0], 7);
+
+        let (elf, degree) = graph.most_conflicted(1)[0];
+        assert_eq!((elf.line, elf.position, degree), (4, 1, 11));
+    }
+
+    #[test]
+    fn test_components() {
+        let input = vec!["1-3,4-5".to_string(), "5-6,8-9".to_string()];
+        let graph = ConflictGraph::new(get_elf_list(&input).unwrap());
+
+        assert_eq!(graph.components, vec![vec![0], vec![1, 2], vec![3]]);
+        assert_eq!(graph.degrees, vec![0, 1, 1, 0]);
+    }
+
+    #[test]
+    fn example_reassign() {
+        let input = read_file("example.txt").unwrap();
+        let elves = get_elf_list(&input).unwrap();
+
+        let reassigned = reassign(&elves).unwrap();
+        let graph = ConflictGraph::new(reassigned.clone());
+
+        assert!(graph.degrees.iter().all(|degree| *degree == 0));
+        assert!(elves
+            .iter()
+            .zip(&reassigned)
+            .all(|(elf, moved)| elf.range.len() == moved.range.len()));
+    }
+
+    #[test]
+    fn test_reassign_extreme_bounds() {
+        let input = vec!["-9223372036854775808-9223372036854775807,1-2".to_string()];
+        let elves = get_elf_list(&input).unwrap();
+
+        assert!(reassign(&elves)
+            .unwrap_err()
+            .to_string()
+            .ends_with("stacked placement runs past 9223372036854775807"));
+
+        let input = vec!["9223372036854775806-9223372036854775807,1-2".to_string()];
+        let reassigned = reassign(&get_elf_list(&input).unwrap()).unwrap();
+
+        assert_eq!(reassigned[1].range, Interval::new(1, 2));
+        assert_eq!(
+            reassigned[0].range,
+            Interval::new(9223372036854775806, 9223372036854775807)
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use crate::interval::Interval;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Coverage {
+    segments: Vec<(Interval, usize)>,
+}
+
+impl Coverage {
+    pub fn new(elves: &[Interval]) -> Self {
+        let mut events: Vec<(i128, isize)> = elves
+            .iter()
+            .filter(|elf| !elf.is_empty())
+            .flat_map(|elf| [(elf.start as i128, 1), (elf.end as i128 + 1, -1)])
+            .collect();
+
+        events.sort();
+
+        let mut segments = vec![];
+        let mut count: isize = 0;
+
+        for (i, (position, delta)) in events.iter().enumerate() {
+            count += delta;
+
+            if let Some((next, _)) = events.get(i + 1) {
+                if next > position {
+                    let segment = Interval::new(*position as i64, (next - 1) as i64);
+
+                    segments.push((segment, count as usize));
+                }
+            }
+        }
+
+        Coverage { segments }
+    }
+
+    pub fn covered(&self) -> u64 {
+        self.segments
+            .iter()
+            .filter(|(_, count)| *count > 0)
+            .map(|(segment, _)| segment.len())
+            .sum()
+    }
+
+    pub fn max_overlap(&self) -> usize {
+        self.segments
+            .iter()
+            .map(|(_, count)| *count)
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn assigned_more_than(&self, k: usize) -> Vec<Interval> {
+        merge(
+            self.segments
+                .iter()
+                .filter(|(_, count)| *count > k)
+                .map(|(segment, _)| *segment),
+        )
+    }
+
+    pub fn gaps(&self, camp: &Interval) -> Vec<Interval> {
+        let covered = self.assigned_more_than(0);
+        let mut gaps = vec![];
+        let mut start = camp.start as i128;
+
+        for segment in covered
+            .iter()
+            .filter_map(|segment| segment.intersection(camp))
+        {
+            if (segment.start as i128) > start {
+                gaps.push(Interval::new(start as i64, segment.start - 1));
+            }
+
+            start = segment.end as i128 + 1;
+        }
+
+        if start <= camp.end as i128 {
+            gaps.push(Interval::new(start as i64, camp.end));
+        }
+
+        gaps
+    }
+
+    pub fn extent(&self) -> Option<Interval> {
+        let first = self.segments.first()?.0;
+        let last = self.segments.last()?.0;
+
+        Some(Interval::new(first.start, last.end))
+    }
+}
+
+fn merge(segments: impl Iterator<Item = Interval>) -> Vec<Interval> {
+    let mut merged: Vec<Interval> = vec![];
+
+    for segment in segments {
+        match merged.last_mut() {
+            Some(last) if last.end as i128 + 1 == segment.start as i128 => last.end = segment.end,
+            _ => merged.push(segment),
+        }
+    }
+
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{get_elves, read_file};
+
+    #[test]
+    fn example_coverage() {
+        let input = read_file("example.txt").unwrap();
+        let coverage = Coverage::new(&get_elves(&input).unwrap());
+
+        assert_eq!(coverage.extent(), Some(Interval::new(2, 9)));
+        assert_eq!(coverage.covered(), 8);
+        assert_eq!(coverage.max_overlap(), 8);
+        assert_eq!(
+            coverage.gaps(&Interval::new(1, 10)),
+            vec![Interval::new(1, 1), Interval::new(10, 10)]
+        );
+        assert_eq!(coverage.assigned_more_than(6), vec![Interval::new(4, 6)]);
+    }
+
+    #[test]
+    fn test_gaps() {
+        let coverage = Coverage::new(&[
+            Interval::new(1, 3),
+            Interval::new(6, 4_000_000_000),
+            Interval::new(2, 2),
+        ]);
+
+        assert_eq!(coverage.covered(), 3 + 4_000_000_000 - 5);
+        assert_eq!(
+            coverage.gaps(&Interval::new(1, 4_000_000_000)),
+            vec![Interval::new(4, 5)]
+        );
+        assert_eq!(coverage.assigned_more_than(1), vec![Interval::new(2, 2)]);
+        assert_eq!(coverage.max_overlap(), 2);
+    }
+}
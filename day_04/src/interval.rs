@@ -0,0 +1,69 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    pub fn new(start: i64, end: i64) -> Self {
+        Interval { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end < self.start
+    }
+
+    pub fn len(&self) -> u64 {
+        if self.is_empty() {
+            return 0;
+        }
+
+        self.end.abs_diff(self.start).saturating_add(1)
+    }
+
+    pub fn contains(&self, other: &Interval) -> bool {
+        other.is_empty() || (self.start <= other.start && other.end <= self.end)
+    }
+
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        !self.is_empty() && !other.is_empty() && self.start <= other.end && other.start <= self.end
+    }
+
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        self.overlaps(other)
+            .then(|| Interval::new(self.start.max(other.start), self.end.min(other.end)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval() {
+        let a = Interval::new(2, 8);
+        let b = Interval::new(3, 7);
+        let c = Interval::new(8, 9);
+
+        assert!(a.contains(&b));
+        assert!(!b.contains(&a));
+        assert!(a.overlaps(&c));
+        assert!(!b.overlaps(&c));
+        assert_eq!(a.intersection(&c), Some(Interval::new(8, 8)));
+        assert_eq!(b.intersection(&c), None);
+        assert_eq!(a.len(), 7);
+        assert_eq!(Interval::new(5, 4).len(), 0);
+    }
+
+    #[test]
+    fn test_huge_interval() {
+        let a = Interval::new(1, 4_000_000_000);
+        let b = Interval::new(2, 3_999_999_999);
+
+        assert!(a.contains(&b));
+        assert!(a.overlaps(&b));
+        assert_eq!(a.intersection(&b), Some(b));
+        assert_eq!(a.len(), 4_000_000_000);
+        assert_eq!(Interval::new(i64::MIN, i64::MAX).len(), u64::MAX);
+    }
+}
@@ -1,57 +1,138 @@
-use std::{fs, ops::RangeInclusive};
+use std::{env, fs};
+
+use assignment::{get_elf_list, Elf};
+use coverage::Coverage;
+use interval::Interval;
+
+mod assignment;
+mod conflicts;
+mod coverage;
+mod interval;
 
 fn main() -> anyhow::Result<()> {
     let input = read_file("input.txt")?;
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        Some("coverage") => run_coverage(&input, &args[1..])?,
+        Some("conflicts") => run_conflicts(&input, &args[1..])?,
+        _ => {
+            warn_reversed(&get_elf_list(&input)?);
 
-    println!("Answer 1: {:?}", part_01(&input));
-    println!("Answer 2: {:?}", part_02(&input));
+            println!("Answer 1: {}", part_01(&input)?);
+            println!("Answer 2: {}", part_02(&input)?);
+        }
+    }
 
     Ok(())
 }
 
-fn read_file(filename: &str) -> std::io::Result<Vec<String>> {
-    let input = fs::read_to_string(filename)?;
-
-    Ok(input.lines().filter_map(|line| line.parse().ok()).collect())
+fn warn_reversed(elves: &[Elf]) {
+    for elf in elves.iter().filter(|elf| elf.reversed) {
+        eprintln!("Warning: reversed bounds for {}", elf);
+    }
 }
 
-fn get_range(elf: &str) -> RangeInclusive<i32> {
-    let (lower, upper) = elf.split_once('-').expect("Invalid input");
+fn run_coverage(input: &[String], args: &[String]) -> anyhow::Result<()> {
+    let k = match args.first() {
+        Some(k) => k.parse()?,
+        None => 1,
+    };
 
-    let lower: i32 = lower.parse().unwrap();
-    let upper: i32 = upper.parse().unwrap();
+    let coverage = Coverage::new(&get_elves(input)?);
+    let camp = match args.get(1..3) {
+        Some([start, end]) => Interval::new(start.parse()?, end.parse()?),
+        _ => coverage.extent().unwrap_or(Interval::new(1, 0)),
+    };
 
-    lower..=upper
+    println!("Camp: {}-{}", camp.start, camp.end);
+    println!("Covered sections: {}", coverage.covered());
+    println!("Max simultaneous overlap: {}", coverage.max_overlap());
+
+    for gap in coverage.gaps(&camp) {
+        println!("Uncovered: {}-{}", gap.start, gap.end);
+    }
+
+    for sections in coverage.assigned_more_than(k) {
+        println!(
+            "Assigned to more than {} elves: {}-{}",
+            k, sections.start, sections.end
+        );
+    }
+
+    Ok(())
 }
 
-fn part_01(input: &Vec<String>) -> usize {
-    input
-        .iter()
-        .filter(|pair| {
-            let (elf1, elf2) = pair.split_once(',').expect("Invalid input");
+fn run_conflicts(input: &[String], args: &[String]) -> anyhow::Result<()> {
+    let n = match args.first() {
+        Some(n) => n.parse()?,
+        None => 5,
+    };
 
-            let elf_range1 = get_range(elf1);
-            let elf_range2 = get_range(elf2);
+    let elves = get_elf_list(input)?;
+    let graph = conflicts::ConflictGraph::new(elves.clone());
 
-            elf_range1.clone().all(|x| elf_range2.contains(&x))
-                || elf_range2.clone().all(|x| elf_range1.contains(&x))
-        })
-        .count()
+    println!("Connected components: {}", graph.components.len());
+
+    for component in &graph.components {
+        let start = component.iter().map(|&i| elves[i].range.start).min();
+        let end = component.iter().map(|&i| elves[i].range.end).max();
+
+        if let (Some(start), Some(end)) = (start, end) {
+            println!("  {} elves covering {}-{}", component.len(), start, end);
+        }
+    }
+
+    println!("Most conflicted elves:");
+    for (elf, degree) in graph.most_conflicted(n) {
+        println!("  {}: {} overlaps", elf, degree);
+    }
+
+    println!("Reassignment:");
+    for (elf, moved) in elves.iter().zip(conflicts::reassign(&elves)?) {
+        if elf.range != moved.range {
+            println!("  {} -> {}-{}", elf, moved.range.start, moved.range.end);
+        }
+    }
+
+    Ok(())
 }
 
-fn part_02(input: &Vec<String>) -> usize {
-    input
-        .iter()
-        .filter(|pair| {
-            let (elf1, elf2) = pair.split_once(',').expect("Invalid input");
+fn read_file(filename: &str) -> std::io::Result<Vec<String>> {
+    let input = fs::read_to_string(filename)?;
+
+    Ok(input.lines().filter_map(|line| line.parse().ok()).collect())
+}
 
-            let elf_range1 = get_range(elf1);
-            let elf_range2 = get_range(elf2);
+fn get_elves(input: &[String]) -> anyhow::Result<Vec<Interval>> {
+    Ok(get_elf_list(input)?.iter().map(|elf| elf.range).collect())
+}
 
-            elf_range1.clone().any(|x| elf_range2.contains(&x))
-                || elf_range2.clone().any(|x| elf_range1.contains(&x))
+fn count_pairs(
+    input: &[String],
+    predicate: fn(&Interval, &Interval) -> bool,
+) -> anyhow::Result<usize> {
+    let elves = get_elf_list(input)?;
+
+    Ok(elves
+        .chunk_by(|a, b| a.line == b.line)
+        .map(|group| {
+            group
+                .iter()
+                .enumerate()
+                .flat_map(|(i, a)| group[i + 1..].iter().map(move |b| (a, b)))
+                .filter(|(a, b)| predicate(&a.range, &b.range))
+                .count()
         })
-        .count()
+        .sum())
+}
+
+fn part_01(input: &[String]) -> anyhow::Result<usize> {
+    count_pairs(input, |a, b| a.contains(b) || b.contains(a))
+}
+
+fn part_02(input: &[String]) -> anyhow::Result<usize> {
+    count_pairs(input, |a, b| a.overlaps(b))
 }
 
 #[cfg(test)]
@@ -62,13 +143,40 @@ mod tests {
     fn example_01() {
         let input = read_file("example.txt").unwrap();
 
-        assert_eq!(part_01(&input), 2);
+        assert_eq!(part_01(&input).unwrap(), 2);
     }
 
     #[test]
     fn example_02() {
         let input = read_file("example.txt").unwrap();
 
-        assert_eq!(part_02(&input), 4);
+        assert_eq!(part_02(&input).unwrap(), 4);
+    }
+
+    #[test]
+    fn test_groups() {
+        let input = vec![
+            "-5--1,-3..0,2..=9".to_string(),
+            "1-10,2-3,4-5,5-6".to_string(),
+            "7".to_string(),
+        ];
+
+        assert_eq!(part_01(&input[..1]).unwrap(), 1);
+        assert_eq!(part_02(&input[..1]).unwrap(), 1);
+        assert_eq!(part_01(&input[1..2]).unwrap(), 3);
+        assert_eq!(part_02(&input[1..2]).unwrap(), 4);
+        assert!(part_01(&input).is_err());
+    }
+
+    #[test]
+    fn test_huge_ranges() {
+        let input = vec![
+            "1-4000000000,2-3999999999".to_string(),
+            "1-2000000000,2000000000-4000000000".to_string(),
+            "1-2000000000,2000000001-4000000000".to_string(),
+        ];
+
+        assert_eq!(part_01(&input).unwrap(), 1);
+        assert_eq!(part_02(&input).unwrap(), 2);
     }
 }
@@ -0,0 +1,167 @@
+use anyhow::bail;
+
+use crate::Instruction;
+
+pub trait Crane {
+    fn lift(&self, stacks: &mut [Vec<String>], instruction: &Instruction) -> anyhow::Result<()>;
+}
+
+fn check_shortfall(stacks: &[Vec<String>], instruction: &Instruction) -> anyhow::Result<()> {
+    let available = stacks[instruction.from - 1].len();
+
+    if available < instruction.quantity {
+        bail!(
+            "Cannot move {} crates from stack {}, it only holds {}",
+            instruction.quantity,
+            instruction.from,
+            available
+        );
+    }
+
+    Ok(())
+}
+
+fn take(stacks: &mut [Vec<String>], instruction: &Instruction) -> anyhow::Result<Vec<String>> {
+    check_shortfall(stacks, instruction)?;
+
+    let from = &mut stacks[instruction.from - 1];
+    let at = from.len() - instruction.quantity;
+
+    Ok(from.split_off(at))
+}
+
+fn put(stacks: &mut [Vec<String>], to: usize, crates: Vec<String>) {
+    stacks[to].extend(crates);
+}
+
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn lift(&self, stacks: &mut [Vec<String>], instruction: &Instruction) -> anyhow::Result<()> {
+        let mut crates = take(stacks, instruction)?;
+        crates.reverse();
+
+        put(stacks, instruction.to - 1, crates);
+
+        Ok(())
+    }
+}
+
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn lift(&self, stacks: &mut [Vec<String>], instruction: &Instruction) -> anyhow::Result<()> {
+        let crates = take(stacks, instruction)?;
+
+        put(stacks, instruction.to - 1, crates);
+
+        Ok(())
+    }
+}
+
+pub struct LiftCapacity(pub usize);
+
+impl Crane for LiftCapacity {
+    fn lift(&self, stacks: &mut [Vec<String>], instruction: &Instruction) -> anyhow::Result<()> {
+        if self.0 == 0 {
+            bail!("Lift capacity must be at least 1");
+        }
+
+        let mut remaining = instruction.quantity;
+
+        while remaining > 0 {
+            let batch = Instruction {
+                quantity: remaining.min(self.0),
+                ..*instruction
+            };
+
+            CrateMover9001.lift(stacks, &batch)?;
+            remaining -= batch.quantity;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct Strict(pub Box<dyn Crane>);
+
+impl Crane for Strict {
+    fn lift(&self, stacks: &mut [Vec<String>], instruction: &Instruction) -> anyhow::Result<()> {
+        check_shortfall(stacks, instruction)?;
+
+        self.0.lift(stacks, instruction)
+    }
+}
+
+pub fn get_crane(name: &str) -> anyhow::Result<Box<dyn Crane>> {
+    Ok(match name.split_once(':') {
+        Some(("capacity", capacity)) => Box::new(LiftCapacity(capacity.parse()?)),
+        Some(("strict", name)) => Box::new(Strict(get_crane(name)?)),
+        None => match name {
+            "9000" => Box::new(CrateMover9000),
+            "9001" => Box::new(CrateMover9001),
+            _ => bail!("Unknown crane {:?}", name),
+        },
+        _ => bail!("Unknown crane {:?}", name),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{read_file, simulate};
+
+    #[test]
+    fn example_lift_capacity() {
+        let input = read_file("example.txt").unwrap();
+
+        assert_eq!(simulate(&input, &LiftCapacity(1)).unwrap(), "CMZ");
+        assert_eq!(simulate(&input, &LiftCapacity(3)).unwrap(), "MCD");
+        assert_eq!(simulate(&input, &LiftCapacity(2)).unwrap(), "MCZ");
+    }
+
+    #[test]
+    fn example_get_crane() {
+        let input = read_file("example.txt").unwrap();
+        let answer = |name: &str| simulate(&input, get_crane(name).unwrap().as_ref()).unwrap();
+
+        assert_eq!(answer("9000"), "CMZ");
+        assert_eq!(answer("9001"), "MCD");
+        assert_eq!(answer("capacity:2"), "MCZ");
+        assert_eq!(answer("strict:capacity:2"), "MCZ");
+
+        assert!(get_crane("9002").is_err());
+        assert!(get_crane("capacity:x").is_err());
+        assert!(get_crane("strict:9002").is_err());
+    }
+
+    #[test]
+    fn test_shortfall() {
+        let instruction = "move 2 from 1 to 2".parse().unwrap();
+        let short = || vec![vec!["A".to_string()], vec![]];
+
+        for crane in [&CrateMover9000 as &dyn Crane, &CrateMover9001] {
+            let mut stacks = short();
+
+            assert_eq!(
+                crane
+                    .lift(&mut stacks, &instruction)
+                    .unwrap_err()
+                    .to_string(),
+                "Cannot move 2 crates from stack 1, it only holds 1"
+            );
+            assert_eq!(stacks, short());
+        }
+
+        let mut stacks = short();
+        assert!(LiftCapacity(1).lift(&mut stacks, &instruction).is_err());
+        assert_eq!(stacks, vec![vec![], vec!["A".to_string()]]);
+
+        let mut stacks = short();
+        assert!(get_crane("strict:capacity:1")
+            .unwrap()
+            .lift(&mut stacks, &instruction)
+            .is_err());
+        assert_eq!(stacks, short());
+    }
+}
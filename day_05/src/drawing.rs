@@ -0,0 +1,172 @@
+use anyhow::bail;
+
+fn get_ids(row: &str, line: usize) -> anyhow::Result<Vec<(usize, usize)>> {
+    let chars: Vec<char> = row.chars().collect();
+    let mut ids = vec![];
+    let mut start = None;
+
+    for (i, c) in chars.iter().chain([&' ']).enumerate() {
+        match (start, c.is_whitespace()) {
+            (None, false) => start = Some(i),
+            (Some(s), true) => {
+                let id: String = chars[s..i].iter().collect();
+
+                if id.parse() != Ok(ids.len() + 1) {
+                    bail!(
+                        "Line {}: expected stack id {}, found {:?}",
+                        line,
+                        ids.len() + 1,
+                        id
+                    );
+                }
+
+                ids.push((s, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    if ids.is_empty() {
+        bail!("Line {}: no stack ids found", line);
+    }
+
+    Ok(ids)
+}
+
+fn get_cells(row: &str, line: usize) -> anyhow::Result<Vec<(usize, usize, String)>> {
+    let chars: Vec<char> = row.chars().collect();
+    let mut cells = vec![];
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '[' => {
+                let end = match chars[i..].iter().position(|c| *c == ']') {
+                    Some(offset) => i + offset,
+                    None => bail!("Line {}: unclosed crate at column {}", line, i + 1),
+                };
+                let label: String = chars[i + 1..end].iter().collect();
+
+                if label.is_empty() || label.contains(char::is_whitespace) || label.contains('[') {
+                    bail!("Line {}: invalid crate label {:?}", line, label);
+                }
+
+                cells.push((i, end + 1, label));
+                i = end;
+            }
+            c if c.is_whitespace() => {}
+            c => bail!("Line {}: unexpected {:?} at column {}", line, c, i + 1),
+        }
+
+        i += 1;
+    }
+
+    Ok(cells)
+}
+
+pub fn get_stacks(input: &[String]) -> anyhow::Result<Vec<Vec<String>>> {
+    let (ids, rows) = match input.split_last() {
+        Some(drawing) => drawing,
+        None => bail!("Missing stack drawing"),
+    };
+
+    let ids = get_ids(ids, input.len())?;
+    let mut stacks: Vec<Vec<String>> = vec![vec![]; ids.len()];
+
+    for (index, row) in rows.iter().enumerate().rev() {
+        let line = index + 1;
+        let mut filled = vec![false; ids.len()];
+
+        for (start, end, label) in get_cells(row, line)? {
+            let below: Vec<usize> = ids
+                .iter()
+                .enumerate()
+                .filter(|(_, (id_start, id_end))| *id_start < end && start < *id_end)
+                .map(|(stack, _)| stack)
+                .collect();
+
+            let stack = match below[..] {
+                [stack] => stack,
+                [] => bail!("Line {}: crate [{}] is not above a stack id", line, label),
+                _ => bail!("Line {}: crate [{}] spans several stack ids", line, label),
+            };
+
+            if filled[stack] {
+                bail!("Line {}: two crates above stack {}", line, stack + 1);
+            }
+
+            if stacks[stack].len() != rows.len() - 1 - index {
+                bail!(
+                    "Line {}: crate [{}] floats above stack {}",
+                    line,
+                    label,
+                    stack + 1
+                );
+            }
+
+            filled[stack] = true;
+            stacks[stack].push(label);
+        }
+    }
+
+    Ok(stacks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(drawing: &str) -> Vec<String> {
+        drawing.lines().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn test_many_stacks_and_labels() {
+        let drawing = lines(
+            "[A]\n\
+             [B] [C] [D] [E] [F] [G] [H] [I] [J] [LM][K]\n \
+              1   2   3   4   5   6   7   8   9   10  11",
+        );
+
+        let stacks = get_stacks(&drawing).unwrap();
+
+        assert_eq!(stacks.len(), 11);
+        assert_eq!(stacks[0], vec!["B", "A"]);
+        assert_eq!(stacks[9], vec!["LM"]);
+        assert_eq!(stacks[10], vec!["K"]);
+    }
+
+    #[test]
+    fn test_short_rows() {
+        let drawing = lines("    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3");
+
+        let stacks = get_stacks(&drawing).unwrap();
+
+        assert_eq!(stacks, vec![vec!["Z", "N"], vec!["M", "C", "D"], vec!["P"]]);
+    }
+
+    #[test]
+    fn test_inconsistent_drawings() {
+        let error = |drawing: &str| get_stacks(&lines(drawing)).unwrap_err().to_string();
+
+        assert_eq!(
+            error("[A]\n    [B]\n 1   2"),
+            "Line 1: crate [A] floats above stack 1"
+        );
+        assert_eq!(
+            error("[ABCDE]\n 1   2"),
+            "Line 1: crate [ABCDE] spans several stack ids"
+        );
+        assert_eq!(
+            error("        [A]\n 1   2"),
+            "Line 1: crate [A] is not above a stack id"
+        );
+        assert_eq!(error("[A] x\n 1   2"), "Line 1: unexpected 'x' at column 5");
+        assert_eq!(error("[A\n 1"), "Line 1: unclosed crate at column 1");
+        assert_eq!(
+            error("[A]\n 1   3"),
+            "Line 2: expected stack id 2, found \"3\""
+        );
+    }
+}
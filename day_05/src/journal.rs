@@ -0,0 +1,204 @@
+use anyhow::bail;
+
+use crate::{check, crane::Crane, Instruction, Step};
+
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub line: usize,
+    pub instruction: Instruction,
+    pub crates: Vec<String>,
+}
+
+pub struct Journal<'a> {
+    crane: &'a dyn Crane,
+    stacks: Vec<Vec<String>>,
+    steps: Vec<Step>,
+    entries: Vec<Entry>,
+}
+
+impl<'a> Journal<'a> {
+    pub fn new(stacks: Vec<Vec<String>>, steps: Vec<Step>, crane: &'a dyn Crane) -> Self {
+        Journal {
+            crane,
+            stacks,
+            steps,
+            entries: vec![],
+        }
+    }
+
+    pub fn stacks(&self) -> &[Vec<String>] {
+        &self.stacks
+    }
+
+    pub fn position(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    pub fn redo(&mut self) -> anyhow::Result<bool> {
+        let (line, instruction) = match self.steps.get(self.position()) {
+            Some(step) => *step,
+            None => return Ok(false),
+        };
+
+        check(&self.stacks, line, &instruction)?;
+
+        let source = &self.stacks[instruction.from - 1];
+        let crates = source[source.len() - instruction.quantity..].to_vec();
+
+        self.crane.lift(&mut self.stacks, &instruction)?;
+        self.entries.push(Entry {
+            line,
+            instruction,
+            crates,
+        });
+
+        Ok(true)
+    }
+
+    pub fn undo(&mut self) -> bool {
+        let entry = match self.entries.pop() {
+            Some(entry) => entry,
+            None => return false,
+        };
+
+        let destination = &mut self.stacks[entry.instruction.to - 1];
+        destination.truncate(destination.len() - entry.instruction.quantity);
+
+        self.stacks[entry.instruction.from - 1].extend(entry.crates);
+
+        true
+    }
+
+    pub fn jump(&mut self, step: usize) -> anyhow::Result<()> {
+        if step > self.steps.len() {
+            bail!(
+                "Step {} is out of range, expected 0 to {}",
+                step,
+                self.steps.len()
+            );
+        }
+
+        while self.position() > step {
+            self.undo();
+        }
+
+        while self.position() < step {
+            self.redo()?;
+        }
+
+        Ok(())
+    }
+}
+
+fn get_permutation(crane: &dyn Crane, quantity: usize) -> anyhow::Result<Vec<usize>> {
+    let mut probe = vec![(0..quantity).map(|i| i.to_string()).collect(), vec![]];
+
+    crane.lift(
+        &mut probe,
+        &Instruction {
+            quantity,
+            from: 1,
+            to: 2,
+        },
+    )?;
+
+    Ok(probe[1].iter().map(|i| i.parse().unwrap()).collect())
+}
+
+pub fn reverse(
+    stacks: &[Vec<String>],
+    steps: &[Step],
+    crane: &dyn Crane,
+) -> anyhow::Result<Vec<Vec<String>>> {
+    let mut stacks = stacks.to_vec();
+
+    for (line, instruction) in steps.iter().rev() {
+        let reversed = Instruction {
+            from: instruction.to,
+            to: instruction.from,
+            ..*instruction
+        };
+
+        check(&stacks, *line, &reversed)?;
+
+        let destination = &mut stacks[instruction.to - 1];
+        let moved = destination.split_off(destination.len() - instruction.quantity);
+        let mut crates = vec![String::new(); instruction.quantity];
+
+        for (label, index) in moved
+            .into_iter()
+            .zip(get_permutation(crane, instruction.quantity)?)
+        {
+            crates[index] = label;
+        }
+
+        stacks[instruction.from - 1].extend(crates);
+    }
+
+    Ok(stacks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        crane::{CrateMover9000, CrateMover9001, LiftCapacity},
+        get_input, read_file, run,
+    };
+
+    #[test]
+    fn example_undo_redo() {
+        let input = read_file("example.txt").unwrap();
+        let (stacks, steps) = get_input(&input).unwrap();
+        let mut journal = Journal::new(stacks.clone(), steps.clone(), &CrateMover9000);
+
+        assert!(!journal.undo());
+        assert!(journal.redo().unwrap());
+        assert_eq!(journal.entries()[0].crates, vec!["D"]);
+
+        journal.jump(4).unwrap();
+        assert!(!journal.redo().unwrap());
+
+        let mut expected = stacks.clone();
+        run(&mut expected, &steps, &CrateMover9000).unwrap();
+        assert_eq!(journal.stacks(), expected);
+
+        journal.jump(0).unwrap();
+        assert_eq!(journal.stacks(), stacks);
+        assert!(journal.jump(5).is_err());
+    }
+
+    #[test]
+    fn example_reverse() {
+        let input = read_file("example.txt").unwrap();
+        let (stacks, steps) = get_input(&input).unwrap();
+
+        for crane in [
+            &CrateMover9000 as &dyn Crane,
+            &CrateMover9001,
+            &LiftCapacity(2),
+        ] {
+            let mut result = stacks.clone();
+            run(&mut result, &steps, crane).unwrap();
+
+            assert_eq!(reverse(&result, &steps, crane).unwrap(), stacks);
+        }
+    }
+
+    #[test]
+    fn test_reverse_shortfall() {
+        let stacks = vec![vec!["A".to_string()], vec![]];
+        let steps = vec![(7, "move 1 from 1 to 2".parse().unwrap())];
+
+        assert_eq!(
+            reverse(&stacks, &steps, &CrateMover9001)
+                .unwrap_err()
+                .to_string(),
+            "Line 7: move 1 from 2 to 1: stack 2 only holds 0 crates, 1 short"
+        );
+    }
+}
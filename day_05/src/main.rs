@@ -1,75 +1,192 @@
-use std::{fs, str::FromStr};
+use std::{env, fmt, fs, str::FromStr, time::Duration};
+
+use anyhow::{anyhow, bail, Context};
+
+use crane::{Crane, CrateMover9000, CrateMover9001};
+
+mod crane;
+mod drawing;
+mod journal;
+mod planner;
+mod render;
 
 fn main() -> anyhow::Result<()> {
     let input = read_file("input.txt")?;
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.iter().map(String::as_str).collect::<Vec<&str>>()[..] {
+        [] => {
+            println!("Answer 1: {}", part_01(&input)?);
+            println!("Answer 2: {}", part_02(&input)?);
+        }
+        ["step", name] => {
+            let (mut stacks, instructions) = get_input(&input)?;
+
+            render::animate(
+                &mut stacks,
+                &instructions,
+                crane::get_crane(name)?.as_ref(),
+                None,
+            )?;
+        }
+        ["animate", name, delay] => {
+            let (mut stacks, instructions) = get_input(&input)?;
+            let delay = Duration::from_millis(delay.parse()?);
+
+            render::animate(
+                &mut stacks,
+                &instructions,
+                crane::get_crane(name)?.as_ref(),
+                Some(delay),
+            )?;
+        }
+        ["jump", name, step] => {
+            let (stacks, instructions) = get_input(&input)?;
+            let crane = crane::get_crane(name)?;
+            let mut journal = journal::Journal::new(stacks, instructions, crane.as_ref());
 
-    println!("Answer 1: {:?}", part_01(&input));
-    println!("Answer 2: {:?}", part_02(&input));
+            journal.jump(step.parse()?)?;
+
+            for (step, entry) in journal.entries().iter().enumerate() {
+                println!(
+                    "Step {} (line {}): {}, moved [{}]",
+                    step + 1,
+                    entry.line,
+                    entry.instruction,
+                    entry.crates.join("] [")
+                );
+            }
+
+            println!("{}", render::render(journal.stacks()));
+        }
+        ["reverse", name, last] => {
+            let (_, instructions) = get_input(&input)?;
+            let crane = crane::get_crane(name)?;
+            let last = read_drawing(last)?;
+
+            let initial = journal::reverse(&last, &instructions, crane.as_ref())?;
+
+            println!("{}", render::render(&initial));
+        }
+        ["plan", name, target] => {
+            let (stacks, _) = get_input(&input)?;
+            let crane = crane::get_crane(name)?;
+            let target = read_drawing(target)?;
+
+            match planner::plan(&stacks, &target, crane.as_ref(), 1_000_000)? {
+                Some(instructions) => instructions
+                    .iter()
+                    .for_each(|instruction| println!("{}", instruction)),
+                None => bail!("Target arrangement is unreachable with this crane"),
+            }
+        }
+        [name] => {
+            let crane = crane::get_crane(name)?;
+
+            println!("Answer: {}", simulate(&input, crane.as_ref())?);
+        }
+        _ => bail!(
+            "Usage: day_05 [<crane> | step <crane> | animate <crane> <delay ms> | jump <crane> <step> | reverse <crane> <final file> | plan <crane> <target file>]"
+        ),
+    }
 
     Ok(())
 }
 
-fn get_stacks(input: &[String]) -> Vec<Vec<char>> {
-    let (ids, stacks) = input.split_last().unwrap();
+fn get_input(input: &[String]) -> anyhow::Result<(Vec<Vec<String>>, Vec<Step>)> {
+    let index = input
+        .iter()
+        .position(|x| x.is_empty())
+        .ok_or_else(|| anyhow!("Missing blank line between drawing and instructions"))?;
+    let (stacks, instructions) = input.split_at(index);
 
-    ids.chars()
+    let stacks = drawing::get_stacks(stacks)?;
+    let instructions = instructions
+        .iter()
         .enumerate()
-        .filter(|(_, c)| !c.is_whitespace())
-        .map(|(i, _)| {
-            stacks
-                .iter()
-                .map(|s| s.chars().collect::<Vec<char>>()[i])
-                .filter(|c| !c.is_whitespace())
-                .collect()
+        .filter(|(_, s)| !s.is_empty())
+        .map(|(offset, s)| {
+            let line = index + offset + 1;
+            let instruction = s.parse().map_err(|e| anyhow!("Line {}: {}", line, e))?;
+
+            Ok((line, instruction))
         })
-        .collect()
+        .collect::<anyhow::Result<_>>()?;
+
+    Ok((stacks, instructions))
 }
 
-fn part_01(input: &Vec<String>) -> String {
-    let index = input.iter().position(|x| x == "").unwrap();
-    let (stacks, instructions) = input.split_at(index);
+fn read_drawing(filename: &str) -> anyhow::Result<Vec<Vec<String>>> {
+    let lines: Vec<String> = read_file(filename)?
+        .into_iter()
+        .take_while(|line| !line.is_empty())
+        .collect();
 
-    let mut stacks = get_stacks(stacks);
+    drawing::get_stacks(&lines)
+}
 
-    instructions
+fn get_tops(stacks: &[Vec<String>]) -> String {
+    stacks
         .iter()
-        .filter(|s| !s.is_empty())
-        .filter_map(|s| s.parse::<Instruction>().ok())
-        .for_each(|instruction| {
-            let removed: Vec<char> = stacks[instruction.from - 1]
-                .drain(..instruction.quantity)
-                .collect();
+        .map(|stack| stack.last().map_or("_", String::as_str))
+        .collect()
+}
 
-            removed
-                .iter()
-                .for_each(|c| stacks[instruction.to - 1].insert(0, *c));
-        });
+fn simulate(input: &[String], crane: &dyn Crane) -> anyhow::Result<String> {
+    let (mut stacks, instructions) = get_input(input)?;
 
-    stacks.iter().map(|c| c[0]).collect()
+    run(&mut stacks, &instructions, crane)?;
+
+    Ok(get_tops(&stacks))
 }
 
-fn part_02(input: &Vec<String>) -> String {
-    let index = input.iter().position(|x| x == "").unwrap();
-    let (stacks, instructions) = input.split_at(index);
+fn check(stacks: &[Vec<String>], line: usize, instruction: &Instruction) -> anyhow::Result<()> {
+    for stack in [instruction.from, instruction.to] {
+        if stack == 0 || stack > stacks.len() {
+            bail!(
+                "Line {}: {}: stack {} does not exist, expected 1 to {}",
+                line,
+                instruction,
+                stack,
+                stacks.len()
+            );
+        }
+    }
 
-    let mut stacks = get_stacks(stacks);
+    let available = stacks[instruction.from - 1].len();
 
-    instructions
-        .iter()
-        .filter(|s| !s.is_empty())
-        .filter_map(|s| s.parse::<Instruction>().ok())
-        .for_each(|instruction| {
-            let removed: Vec<char> = stacks[instruction.from - 1]
-                .drain(..instruction.quantity)
-                .collect();
+    if available < instruction.quantity {
+        bail!(
+            "Line {}: {}: stack {} only holds {} crates, {} short",
+            line,
+            instruction,
+            instruction.from,
+            available,
+            instruction.quantity - available
+        );
+    }
 
-            removed
-                .iter()
-                .rev()
-                .for_each(|c| stacks[instruction.to - 1].insert(0, *c));
-        });
+    Ok(())
+}
+
+fn run(stacks: &mut [Vec<String>], instructions: &[Step], crane: &dyn Crane) -> anyhow::Result<()> {
+    for (line, instruction) in instructions {
+        check(stacks, *line, instruction)?;
 
-    stacks.iter().map(|c| c[0]).collect()
+        crane
+            .lift(stacks, instruction)
+            .map_err(|e| anyhow!("Line {}: {}", line, e))?;
+    }
+
+    Ok(())
+}
+
+fn part_01(input: &[String]) -> anyhow::Result<String> {
+    simulate(input, &CrateMover9000)
+}
+
+fn part_02(input: &[String]) -> anyhow::Result<String> {
+    simulate(input, &CrateMover9001)
 }
 
 fn read_file(filename: &str) -> std::io::Result<Vec<String>> {
@@ -78,45 +195,202 @@ fn read_file(filename: &str) -> std::io::Result<Vec<String>> {
     Ok(input.lines().filter_map(|line| line.parse().ok()).collect())
 }
 
-#[derive(Debug)]
+type Step = (usize, Instruction);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct Instruction {
     quantity: usize,
     from: usize,
     to: usize,
 }
 
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.quantity, self.from, self.to
+        )
+    }
+}
+
 impl FromStr for Instruction {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let input: Vec<usize> = s
-            .split_whitespace()
-            .filter_map(|s| s.parse().ok())
-            .collect();
-
-        Ok(Instruction {
-            quantity: input[0],
-            from: input[1],
-            to: input[2],
-        })
+        match s.split_whitespace().collect::<Vec<&str>>()[..] {
+            ["move", quantity, "from", from, "to", to] => Ok(Instruction {
+                quantity: quantity
+                    .parse()
+                    .with_context(|| format!("invalid quantity {:?}", quantity))?,
+                from: from
+                    .parse()
+                    .with_context(|| format!("invalid stack {:?}", from))?,
+                to: to
+                    .parse()
+                    .with_context(|| format!("invalid stack {:?}", to))?,
+            }),
+            _ => bail!(
+                "invalid instruction {:?}, expected \"move N from A to B\"",
+                s
+            ),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::Instant;
 
     #[test]
     fn example_01() {
         let input = read_file("example.txt").unwrap();
 
-        assert_eq!(part_01(&input), "CMZ");
+        assert_eq!(part_01(&input).unwrap(), "CMZ");
     }
 
     #[test]
     fn example_02() {
         let input = read_file("example.txt").unwrap();
 
-        assert_eq!(part_02(&input), "MCD");
+        assert_eq!(part_02(&input).unwrap(), "MCD");
+    }
+
+    #[test]
+    fn test_invalid_instructions() {
+        let input = read_file("example.txt").unwrap();
+        let with_line = |line: &str| {
+            let mut input = input.clone();
+            input[6] = line.to_string();
+            part_01(&input).unwrap_err().to_string()
+        };
+
+        assert_eq!(
+            with_line("move 4 from 1 to 2"),
+            "Line 7: move 4 from 1 to 2: stack 1 only holds 3 crates, 1 short"
+        );
+        assert_eq!(
+            with_line("move 1 from 0 to 2"),
+            "Line 7: move 1 from 0 to 2: stack 0 does not exist, expected 1 to 3"
+        );
+        assert_eq!(
+            with_line("move 1 from 1 to 4"),
+            "Line 7: move 1 from 1 to 4: stack 4 does not exist, expected 1 to 3"
+        );
+        assert_eq!(
+            with_line("move 1 from 1"),
+            "Line 7: invalid instruction \"move 1 from 1\", expected \"move N from A to B\""
+        );
+        assert_eq!(
+            with_line("move x from 1 to 2"),
+            "Line 7: invalid quantity \"x\""
+        );
+    }
+
+    #[test]
+    fn test_empty_stack_in_answer() {
+        let mut input = read_file("example.txt").unwrap();
+        input.truncate(7);
+
+        assert_eq!(part_01(&input).unwrap(), "_CZ");
+    }
+
+    fn generate(stack_count: usize, height: usize, moves: usize) -> (Vec<Vec<String>>, Vec<Step>) {
+        let mut seed: u64 = 2022;
+        let mut next = move || {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) as usize
+        };
+
+        let stacks = (0..stack_count)
+            .map(|_| {
+                (0..height)
+                    .map(|_| ((b'A' + (next() % 26) as u8) as char).to_string())
+                    .collect()
+            })
+            .collect();
+        let mut heights = vec![height; stack_count];
+        let instructions = (0..moves)
+            .map(|line| {
+                let from = next() % stack_count;
+                let to = next() % stack_count;
+                let quantity = (1 + next() % 8).min(heights[from]);
+
+                heights[from] -= quantity;
+                heights[to] += quantity;
+
+                let instruction = Instruction {
+                    quantity,
+                    from: from + 1,
+                    to: to + 1,
+                };
+
+                (line + 1, instruction)
+            })
+            .collect();
+
+        (stacks, instructions)
+    }
+
+    fn run_top_first(
+        stacks: &[Vec<String>],
+        instructions: &[Step],
+        reverse: bool,
+    ) -> Vec<Vec<String>> {
+        let mut stacks: Vec<Vec<String>> = stacks
+            .iter()
+            .map(|stack| stack.iter().rev().cloned().collect())
+            .collect();
+
+        for (_, instruction) in instructions {
+            let mut removed: Vec<String> = stacks[instruction.from - 1]
+                .drain(..instruction.quantity)
+                .collect();
+
+            if reverse {
+                removed.reverse();
+            }
+
+            removed
+                .into_iter()
+                .for_each(|c| stacks[instruction.to - 1].insert(0, c));
+        }
+
+        stacks
+            .iter()
+            .map(|stack| stack.iter().rev().cloned().collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_matches_top_first() {
+        let (stacks, instructions) = generate(50, 20, 20_000);
+
+        for (crane, reverse) in [
+            (&CrateMover9000 as &dyn Crane, false),
+            (&CrateMover9001, true),
+        ] {
+            let mut result = stacks.clone();
+            run(&mut result, &instructions, crane).unwrap();
+
+            assert_eq!(result, run_top_first(&stacks, &instructions, reverse));
+        }
+    }
+
+    #[test]
+    #[ignore]
+    fn bench_huge_move_list() {
+        let (stacks, instructions) = generate(5_000, 100, 2_000_000);
+
+        for crane in [&CrateMover9000 as &dyn Crane, &CrateMover9001] {
+            let mut stacks = stacks.clone();
+
+            let start = Instant::now();
+            run(&mut stacks, &instructions, crane).unwrap();
+            println!("{} moves: {:?}", instructions.len(), start.elapsed());
+        }
     }
 }
@@ -0,0 +1,136 @@
+use std::collections::{HashSet, VecDeque};
+
+use anyhow::bail;
+
+use crate::{crane::Crane, Instruction};
+
+fn get_crates(stacks: &[Vec<String>]) -> Vec<String> {
+    let mut crates: Vec<String> = stacks.iter().flatten().cloned().collect();
+    crates.sort();
+
+    crates
+}
+
+pub fn plan(
+    start: &[Vec<String>],
+    target: &[Vec<String>],
+    crane: &dyn Crane,
+    limit: usize,
+) -> anyhow::Result<Option<Vec<Instruction>>> {
+    if start.len() != target.len() {
+        bail!(
+            "Start has {} stacks but target has {}",
+            start.len(),
+            target.len()
+        );
+    }
+
+    if get_crates(start) != get_crates(target) {
+        bail!("Start and target do not hold the same crates");
+    }
+
+    let mut states = vec![start.to_vec()];
+    let mut parents: Vec<Option<(usize, Instruction)>> = vec![None];
+    let mut seen = HashSet::from([start.to_vec()]);
+    let mut queue = VecDeque::from([0]);
+
+    while let Some(current) = queue.pop_front() {
+        if states[current] == target {
+            let mut instructions = vec![];
+            let mut index = current;
+
+            while let Some((parent, instruction)) = parents[index] {
+                instructions.push(instruction);
+                index = parent;
+            }
+
+            instructions.reverse();
+
+            return Ok(Some(instructions));
+        }
+
+        for from in 0..start.len() {
+            for to in (0..start.len()).filter(|to| *to != from) {
+                for quantity in 1..=states[current][from].len() {
+                    let instruction = Instruction {
+                        quantity,
+                        from: from + 1,
+                        to: to + 1,
+                    };
+
+                    let mut next = states[current].clone();
+                    crane.lift(&mut next, &instruction)?;
+
+                    if seen.contains(&next) {
+                        continue;
+                    }
+
+                    if states.len() >= limit {
+                        bail!(
+                            "Search exceeded {} states without reaching the target",
+                            limit
+                        );
+                    }
+
+                    seen.insert(next.clone());
+                    queue.push_back(states.len());
+                    states.push(next);
+                    parents.push(Some((current, instruction)));
+                }
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        crane::{CrateMover9000, CrateMover9001},
+        get_input, read_file, run,
+    };
+
+    #[test]
+    fn example_plan_round_trip() {
+        let input = read_file("example.txt").unwrap();
+        let (start, steps) = get_input(&input).unwrap();
+
+        for crane in [&CrateMover9000 as &dyn Crane, &CrateMover9001] {
+            let mut target = start.clone();
+            run(&mut target, &steps, crane).unwrap();
+
+            let instructions = plan(&start, &target, crane, 100_000).unwrap().unwrap();
+            assert!(instructions.len() <= steps.len());
+
+            let replayed: Vec<(usize, Instruction)> = instructions
+                .iter()
+                .enumerate()
+                .map(|(line, instruction)| (line + 1, instruction.to_string().parse().unwrap()))
+                .collect();
+
+            let mut result = start.clone();
+            run(&mut result, &replayed, crane).unwrap();
+            assert_eq!(result, target);
+        }
+    }
+
+    #[test]
+    fn test_plan_errors() {
+        let start = vec![vec!["A".to_string()], vec![]];
+
+        assert_eq!(
+            plan(&start, &start, &CrateMover9000, 10).unwrap(),
+            Some(vec![])
+        );
+        assert!(plan(
+            &start,
+            &[vec![], vec!["B".to_string()]],
+            &CrateMover9000,
+            10
+        )
+        .is_err());
+        assert!(plan(&start, &[vec![]], &CrateMover9000, 10).is_err());
+    }
+}
@@ -0,0 +1,106 @@
+use std::{thread, time::Duration};
+
+use crate::{crane::Crane, run, Step};
+
+pub fn render(stacks: &[Vec<String>]) -> String {
+    let height = stacks.iter().map(|stack| stack.len()).max().unwrap_or(0);
+    let width = stacks
+        .iter()
+        .flatten()
+        .map(|label| label.chars().count() + 2)
+        .chain([stacks.len().to_string().len() + 2])
+        .max()
+        .unwrap_or(3);
+
+    let mut rows: Vec<String> = (0..height)
+        .rev()
+        .map(|level| {
+            stacks
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(label) => format!("{:<width$}", format!("[{}]", label)),
+                    None => " ".repeat(width),
+                })
+                .collect::<Vec<String>>()
+                .join(" ")
+        })
+        .collect();
+
+    rows.push(
+        (1..=stacks.len())
+            .map(|id| format!("{:^width$}", id))
+            .collect::<Vec<String>>()
+            .join(" "),
+    );
+
+    rows.join("\n")
+}
+
+pub fn animate(
+    stacks: &mut [Vec<String>],
+    instructions: &[Step],
+    crane: &dyn Crane,
+    delay: Option<Duration>,
+) -> anyhow::Result<()> {
+    println!("{}\n", render(stacks));
+
+    for (step, (line, instruction)) in instructions.iter().enumerate() {
+        run(stacks, &[(*line, *instruction)], crane)?;
+
+        if delay.is_some() {
+            print!("\x1b[2J\x1b[H");
+        }
+
+        println!("Step {}: {}", step + 1, instruction);
+        println!("{}\n", render(stacks));
+
+        if let Some(delay) = delay {
+            thread::sleep(delay);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{drawing::get_stacks, get_input, read_file};
+
+    #[test]
+    fn example_round_trip() {
+        let input = read_file("example.txt").unwrap();
+        let (stacks, _) = get_input(&input).unwrap();
+
+        assert_eq!(render(&stacks), input[..4].join("\n"));
+    }
+
+    #[test]
+    fn test_render_empty_stack() {
+        let stacks = vec![
+            vec![],
+            vec!["A".to_string(), "B".to_string()],
+            vec!["C".to_string()],
+        ];
+
+        assert_eq!(render(&stacks), "    [B]    \n    [A] [C]\n 1   2   3 ");
+    }
+
+    #[test]
+    fn test_wide_round_trip() {
+        let mut stacks: Vec<Vec<String>> = (1..=12).map(|id| vec![format!("X{}", id)]).collect();
+        stacks[0].push("LONG".to_string());
+        stacks[1].push("É".to_string());
+        stacks[2].push("ÄÖÜ".to_string());
+
+        let drawing: Vec<String> = render(&stacks).lines().map(String::from).collect();
+
+        assert_eq!(get_stacks(&drawing).unwrap(), stacks);
+
+        let stacks = vec![vec!["É".to_string()]; 4];
+        let drawing: Vec<String> = render(&stacks).lines().map(String::from).collect();
+
+        assert_eq!(drawing[0], "[É] [É] [É] [É]");
+        assert_eq!(get_stacks(&drawing).unwrap(), stacks);
+    }
+}
@@ -1,10 +1,84 @@
-use std::{collections::HashSet, fs};
+use std::{env, fs, io, net::TcpListener};
+
+use anyhow::bail;
+
+mod marker;
+mod stream;
 
 fn main() -> anyhow::Result<()> {
     let input = read_file("input.txt")?;
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.iter().map(String::as_str).collect::<Vec<&str>>()[..] {
+        [] => {
+            for (answer, markers) in [(1, part_01(&input)), (2, part_02(&input))] {
+                println!("Answer {}:", answer);
 
-    println!("Answer 1: {:?}", part_01(&input));
-    println!("Answer 2: {:?}", part_02(&input));
+                for ((line, _), marker) in get_datastreams(&input).zip(markers) {
+                    match marker {
+                        Some(position) => println!("  Line {}: {}", line, position),
+                        None => println!("  Line {}: no marker found", line),
+                    }
+                }
+            }
+        }
+        ["markers", offset] => {
+            let offset = offset.parse()?;
+
+            for (line, datastream) in get_datastreams(&input) {
+                let positions: Vec<usize> = marker::markers(datastream, offset).collect();
+
+                println!("Line {}:", line);
+                println!("  First: {:?}", positions.first());
+                println!("  Count: {}", positions.len());
+                println!("  Positions: {:?}", positions);
+            }
+        }
+        ["longest"] => {
+            for (line, datastream) in get_datastreams(&input) {
+                let run = marker::longest_run(datastream.chars());
+
+                println!(
+                    "Line {}: longest distinct run: {} characters from {} to {}: {}",
+                    line,
+                    run.len,
+                    run.start + 1,
+                    run.start + run.len,
+                    datastream
+                        .chars()
+                        .skip(run.start)
+                        .take(run.len)
+                        .collect::<String>()
+                );
+            }
+        }
+        ["tokens", offset] => {
+            let offset = offset.parse()?;
+
+            for (line, datastream) in get_datastreams(&input) {
+                let tokens: Vec<&str> = datastream.split_whitespace().collect();
+
+                println!(
+                    "Line {}: {:?}",
+                    line,
+                    marker::first_distinct_window(&tokens, offset)
+                );
+            }
+        }
+        ["stream", "-"] => stream::scan(io::stdin().lock(), 4096, |event| println!("{}", event))?,
+        ["stream", path] => {
+            stream::scan(fs::File::open(path)?, 4096, |event| println!("{}", event))?
+        }
+        ["listen", address] => {
+            let (connection, peer) = TcpListener::bind(address)?.accept()?;
+
+            println!("Connection from {}", peer);
+            stream::scan(connection, 4096, |event| println!("{}", event))?;
+        }
+        _ => bail!(
+            "Usage: day_06 [markers <window> | longest | tokens <window> | stream <file or -> | listen <address>]"
+        ),
+    }
 
     Ok(())
 }
@@ -15,43 +89,122 @@ fn read_file(filename: &str) -> std::io::Result<Vec<String>> {
     Ok(input.lines().filter_map(|line| line.parse().ok()).collect())
 }
 
-fn get_start_of_packet_marker(input: &String, offset: usize) -> usize {
-    let mut sequence_length: usize = 0;
-    let chars = input.chars().collect::<Vec<char>>();
-
-    for (index, window) in chars.windows(offset).enumerate() {
-        if window.iter().collect::<HashSet<&char>>().len() == offset {
-            sequence_length = index + offset;
-            break;
-        }
+fn get_start_of_packet_marker(input: &str, offset: usize) -> Option<usize> {
+    if input.is_ascii() {
+        marker::first_distinct_bytes(input.as_bytes(), offset)
+    } else {
+        marker::markers(input, offset).next()
     }
+}
+
+fn get_datastreams(input: &[String]) -> impl Iterator<Item = (usize, &str)> {
+    input
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(index, line)| (index + 1, line.as_str()))
+}
 
-    sequence_length
+fn get_markers(input: &[String], offset: usize) -> Vec<Option<usize>> {
+    get_datastreams(input)
+        .map(|(_, datastream)| get_start_of_packet_marker(datastream, offset))
+        .collect()
 }
 
-fn part_01(input: &Vec<String>) -> usize {
-    get_start_of_packet_marker(&input[0], 4)
+fn part_01(input: &[String]) -> Vec<Option<usize>> {
+    get_markers(input, 4)
 }
 
-fn part_02(input: &Vec<String>) -> usize {
-    get_start_of_packet_marker(&input[0], 14)
+fn part_02(input: &[String]) -> Vec<Option<usize>> {
+    get_markers(input, 14)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::{collections::HashSet, time::Instant};
 
     #[test]
     fn example_01() {
         let input = read_file("example.txt").unwrap();
 
-        assert_eq!(part_01(&input), 7);
+        assert_eq!(
+            part_01(&input),
+            vec![Some(7), Some(5), Some(6), Some(10), Some(11)]
+        );
     }
 
     #[test]
     fn example_02() {
         let input = read_file("example.txt").unwrap();
 
-        assert_eq!(part_02(&input), 7);
+        assert_eq!(
+            part_02(&input),
+            vec![Some(19), Some(23), Some(23), Some(29), Some(26)]
+        );
+    }
+
+    fn generate(length: usize, alphabet: u32) -> String {
+        let mut seed: u64 = 2022;
+
+        (0..length)
+            .map(|_| {
+                seed = seed
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                char::from_u32(0x21 + (seed >> 33) as u32 % alphabet).unwrap()
+            })
+            .collect()
+    }
+
+    fn get_marker_reference(input: &str, offset: usize) -> Option<usize> {
+        let chars = input.chars().collect::<Vec<char>>();
+
+        chars
+            .windows(offset)
+            .position(|window| window.iter().collect::<HashSet<&char>>().len() == offset)
+            .map(|index| index + offset)
+    }
+
+    #[test]
+    fn test_matches_reference() {
+        for alphabet in [4, 8, 26, 40] {
+            let input = generate(5_000, alphabet);
+
+            for offset in 1..=alphabet as usize + 1 {
+                assert_eq!(
+                    get_start_of_packet_marker(&input, offset),
+                    get_marker_reference(&input, offset)
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[ignore]
+    fn bench_large_stream() {
+        for offset in [4, 14, 26, 40, 64] {
+            let input = generate(4_000_000, offset as u32 - 1);
+
+            let start = Instant::now();
+            let expected = get_marker_reference(&input, offset);
+            let reference = start.elapsed();
+
+            let start = Instant::now();
+            let chars = marker::markers(&input, offset).next();
+            let chars_elapsed = start.elapsed();
+
+            let start = Instant::now();
+            let bytes = marker::first_distinct_bytes(input.as_bytes(), offset);
+            let bytes_elapsed = start.elapsed();
+
+            println!(
+                "window {}: HashSet per window: {:?}, chars: {:?}, bytes: {:?}",
+                offset, reference, chars_elapsed, bytes_elapsed
+            );
+
+            assert_eq!(chars, expected);
+            assert_eq!(bytes, expected);
+        }
     }
 }
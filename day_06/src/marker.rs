@@ -0,0 +1,183 @@
+use std::{collections::HashMap, hash::Hash};
+
+pub struct Markers<I: Iterator> {
+    items: I,
+    last_seen: HashMap<I::Item, usize>,
+    index: usize,
+    start: usize,
+    offset: usize,
+}
+
+impl<I> Iterator for Markers<I>
+where
+    I: Iterator,
+    I::Item: Eq + Hash,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        for item in self.items.by_ref() {
+            if let Some(previous) = self.last_seen.insert(item, self.index) {
+                self.start = self.start.max(previous + 1);
+            }
+
+            self.index += 1;
+
+            if self.offset > 0 && self.index - self.start >= self.offset {
+                return Some(self.index);
+            }
+        }
+
+        None
+    }
+}
+
+pub fn distinct_windows<I>(items: I, offset: usize) -> Markers<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: Eq + Hash,
+{
+    Markers {
+        items: items.into_iter(),
+        last_seen: HashMap::new(),
+        index: 0,
+        start: 0,
+        offset,
+    }
+}
+
+pub fn first_distinct_window<T: Eq + Hash>(items: &[T], offset: usize) -> Option<usize> {
+    distinct_windows(items, offset).next()
+}
+
+pub struct Detector {
+    offset: usize,
+    last_seen: [usize; 256],
+    index: usize,
+    start: usize,
+    marker: Option<usize>,
+}
+
+impl Detector {
+    pub fn new(offset: usize) -> Self {
+        Detector {
+            offset,
+            last_seen: [0; 256],
+            index: 0,
+            start: 0,
+            marker: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Detector::new(self.offset);
+    }
+
+    pub fn push(&mut self, byte: u8) -> Option<usize> {
+        if self.marker.is_some() {
+            return None;
+        }
+
+        self.start = self.start.max(self.last_seen[byte as usize]);
+        self.index += 1;
+        self.last_seen[byte as usize] = self.index;
+
+        if self.offset > 0 && self.index - self.start >= self.offset {
+            self.marker = Some(self.index);
+        }
+
+        self.marker
+    }
+}
+
+pub fn first_distinct_bytes(bytes: &[u8], offset: usize) -> Option<usize> {
+    let mut detector = Detector::new(offset);
+
+    bytes.iter().find_map(|byte| detector.push(*byte))
+}
+
+pub fn markers(input: &str, offset: usize) -> Markers<std::str::Chars<'_>> {
+    distinct_windows(input.chars(), offset)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Run {
+    pub start: usize,
+    pub len: usize,
+}
+
+pub fn longest_run<I>(items: I) -> Run
+where
+    I: IntoIterator,
+    I::Item: Eq + Hash,
+{
+    let mut last_seen = HashMap::new();
+    let mut start = 0;
+    let mut longest = Run { start: 0, len: 0 };
+
+    for (index, item) in items.into_iter().enumerate() {
+        if let Some(previous) = last_seen.insert(item, index) {
+            start = start.max(previous + 1);
+        }
+
+        if index + 1 - start > longest.len {
+            longest = Run {
+                start,
+                len: index + 1 - start,
+            };
+        }
+    }
+
+    longest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_markers() {
+        assert_eq!(
+            markers("abcabcdd", 3).collect::<Vec<usize>>(),
+            vec![3, 4, 5, 6, 7]
+        );
+        assert_eq!(markers("aaaa", 2).next(), None);
+        assert_eq!(markers("abc", 0).next(), None);
+    }
+
+    #[test]
+    fn test_longest_run() {
+        assert_eq!(
+            longest_run("mjqjpqmgbljsphdztnvjfqwrcgsmlb".chars()),
+            Run { start: 12, len: 18 }
+        );
+        assert_eq!(longest_run("".chars()), Run { start: 0, len: 0 });
+        assert_eq!(longest_run([1, 2, 1, 3, 4]), Run { start: 1, len: 4 });
+    }
+
+    #[test]
+    fn test_generic_items() {
+        let tokens = ["GET", "GET", "PUT", "POST", "GET", "DELETE"];
+
+        assert_eq!(first_distinct_window(&tokens, 3), Some(4));
+        assert_eq!(first_distinct_window(&tokens, 4), Some(6));
+        assert_eq!(first_distinct_window(&[0u16, 0, 0], 2), None);
+        assert_eq!(
+            distinct_windows(vec![7u64, 7, 8, 9, 8], 2).collect::<Vec<usize>>(),
+            vec![3, 4, 5]
+        );
+    }
+
+    #[test]
+    fn test_bytes_and_unicode() {
+        let input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+
+        assert_eq!(first_distinct_bytes(input.as_bytes(), 14), Some(19));
+        assert_eq!(first_distinct_bytes(&[0, 255, 0, 1], 3), Some(4));
+        assert_eq!(first_distinct_bytes(b"", 1), None);
+        assert_eq!(Detector::new(0).push(b'a'), None);
+
+        assert_eq!(markers("ééaé", 2).next(), Some(3));
+        assert_eq!(first_distinct_bytes("ééaé".as_bytes(), 2), Some(2));
+    }
+}
@@ -0,0 +1,153 @@
+use std::{
+    fmt,
+    io::{self, Read},
+};
+
+use crate::marker::Detector;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Event {
+    Packet { line: usize, position: usize },
+    Message { line: usize, position: usize },
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Event::Packet { line, position } => write!(
+                f,
+                "Line {}: start-of-packet marker after {}",
+                line, position
+            ),
+            Event::Message { line, position } => write!(
+                f,
+                "Line {}: start-of-message marker after {}",
+                line, position
+            ),
+        }
+    }
+}
+
+pub fn scan<R: Read>(
+    mut reader: R,
+    chunk_size: usize,
+    mut emit: impl FnMut(Event),
+) -> io::Result<()> {
+    let mut packet = Detector::new(4);
+    let mut message = Detector::new(14);
+    let mut line = 1;
+    let mut buffer = vec![0; chunk_size];
+
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+
+        for byte in &buffer[..read] {
+            match byte {
+                b'\n' => {
+                    packet.reset();
+                    message.reset();
+                    line += 1;
+                }
+                b'\r' => {}
+                byte => {
+                    if let Some(position) = packet.push(*byte) {
+                        emit(Event::Packet { line, position });
+                    }
+
+                    if let Some(position) = message.push(*byte) {
+                        emit(Event::Message { line, position });
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::Write,
+        net::{TcpListener, TcpStream},
+        thread,
+    };
+
+    const EXAMPLE: &[u8] = b"mjqjpqmgbljsphdztnvjfqwrcgsmlb\n\
+        bvwbjplbgvbhsrlpgdmjqwftvncz\r\n\
+        nppdvjthqldpwncqszvftbrmjlhg\n\
+        \n\
+        nznrnfrfntjfmvfwmzdfjlvtqnbhcprsvw\n\
+        zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw";
+
+    fn expected() -> Vec<Event> {
+        [(1, 7, 19), (2, 5, 23), (3, 6, 23), (5, 10, 29), (6, 11, 26)]
+            .into_iter()
+            .flat_map(|(line, packet, message)| {
+                [
+                    Event::Packet {
+                        line,
+                        position: packet,
+                    },
+                    Event::Message {
+                        line,
+                        position: message,
+                    },
+                ]
+            })
+            .collect()
+    }
+
+    fn collect<R: Read>(reader: R, chunk_size: usize) -> Vec<Event> {
+        let mut events = vec![];
+
+        scan(reader, chunk_size, |event| events.push(event)).unwrap();
+
+        events
+    }
+
+    #[test]
+    fn example_every_chunk_size() {
+        for chunk_size in 1..=EXAMPLE.len() + 1 {
+            assert_eq!(collect(EXAMPLE, chunk_size), expected());
+        }
+    }
+
+    #[test]
+    fn test_no_marker() {
+        assert_eq!(collect(&b"aabbaabb"[..], 3), vec![]);
+        assert_eq!(collect(&b"abc\ndef"[..], 2), vec![]);
+    }
+
+    #[test]
+    fn example_over_tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let writer = thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+
+            for chunk in EXAMPLE.chunks(5) {
+                if stream
+                    .write_all(chunk)
+                    .and_then(|_| stream.flush())
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+
+        let (stream, _) = listener.accept().unwrap();
+
+        assert_eq!(collect(stream, 4096), expected());
+
+        writer.join().unwrap();
+    }
+}
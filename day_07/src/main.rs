@@ -1,62 +1,49 @@
-use std::{collections::BTreeMap, fs, path::PathBuf, str::FromStr};
+use std::{fs, str::FromStr};
+
+use anyhow::{anyhow, bail};
+
+use tree::Tree;
+
+mod tree;
 
 fn main() -> anyhow::Result<()> {
     let input = read_file("input.txt")?;
 
-    println!("Answer 1: {:?}", part_01(&input));
-    println!("Answer 2: {:?}", part_02(&input));
+    println!("Answer 1: {}", part_01(&input)?);
+    println!("Answer 2: {}", part_02(&input)?);
 
     Ok(())
 }
 
-fn part_01(input: &Vec<String>) -> usize {
-    let commands: Vec<Command> = input.iter().filter_map(|c| c.parse().ok()).collect();
-
-    let file_tree = calc_directory_sizes(commands);
-
-    file_tree
-        .iter()
-        .filter_map(|(_, entry)| match entry {
-            DirectoryContent::Directory(dir) => {
-                if dir.size <= 100_000 {
-                    return Some(dir.size);
-                }
+fn part_01(input: &[String]) -> anyhow::Result<usize> {
+    let file_tree = calc_directory_sizes(&get_commands(input)?)?;
 
-                None
-            }
-            _ => None,
-        })
-        .sum()
+    Ok(file_tree
+        .directories()
+        .map(|dir| dir.size)
+        .filter(|size| *size <= 100_000)
+        .sum())
 }
 
-fn part_02(input: &Vec<String>) -> usize {
-    let commands: Vec<Command> = input.iter().filter_map(|c| c.parse().ok()).collect();
+fn part_02(input: &[String]) -> anyhow::Result<usize> {
+    let file_tree = calc_directory_sizes(&get_commands(input)?)?;
 
-    let file_tree = calc_directory_sizes(commands);
+    let available_space: usize = 70000000;
+    let required_space: usize = 30000000;
+    let used_space = file_tree.size(tree::ROOT);
+    let unused_space = available_space.saturating_sub(used_space);
+    let delete_space = required_space.saturating_sub(unused_space);
 
-    let available_space = 70000000;
-    let required_space = 30000000;
-    let used_space = match &file_tree["/"] {
-        DirectoryContent::Directory(dir) => dir.size,
-        _ => panic!("Should be directory"),
-    };
-    let unused_space = available_space - used_space;
-    let delete_space = required_space - unused_space;
+    if delete_space == 0 {
+        return Ok(0);
+    }
 
     file_tree
-        .iter()
-        .filter_map(|(_, entry)| match entry {
-            DirectoryContent::Directory(dir) => {
-                if dir.size >= delete_space {
-                    return Some(dir.size);
-                }
-
-                None
-            }
-            _ => None,
-        })
+        .directories()
+        .map(|dir| dir.size)
+        .filter(|size| *size >= delete_space)
         .min()
-        .unwrap()
+        .ok_or_else(|| anyhow!("No directory frees {} bytes", delete_space))
 }
 
 fn read_file(filename: &str) -> std::io::Result<Vec<String>> {
@@ -65,53 +52,34 @@ fn read_file(filename: &str) -> std::io::Result<Vec<String>> {
     Ok(input.lines().filter_map(|line| line.parse().ok()).collect())
 }
 
-fn calc_directory_sizes(commands: Vec<Command>) -> BTreeMap<String, DirectoryContent> {
-    let mut path = PathBuf::new();
-    let mut file_tree = BTreeMap::<String, DirectoryContent>::new();
-
-    file_tree.insert(
-        "/".to_string(),
-        DirectoryContent::Directory(Directory {
-            name: "/".to_string(),
-            size: 0,
-        }),
-    );
-
-    commands
+fn get_commands(input: &[String]) -> anyhow::Result<Vec<(usize, Command)>> {
+    input
         .iter()
-        .for_each(|command| command.execute(&mut file_tree, &mut path));
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let command = line
+                .parse()
+                .map_err(|e| anyhow!("Line {}: {}", index + 1, e))?;
+
+            Ok((index + 1, command))
+        })
+        .collect()
+}
 
-    let mut files = file_tree
-        .iter()
-        .map(|(path, entry)| (path.clone(), entry.clone()))
-        .collect::<Vec<_>>();
+fn calc_directory_sizes(commands: &[(usize, Command)]) -> anyhow::Result<Tree> {
+    let mut file_tree = Tree::new();
+    let mut cwd = tree::ROOT;
 
-    files.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()));
+    for (line, command) in commands {
+        command
+            .execute(&mut file_tree, &mut cwd)
+            .map_err(|e| anyhow!("Line {}: {}", line, e))?;
+    }
 
-    files
-        .iter()
-        .filter_map(|(path, entry)| match entry {
-            DirectoryContent::Directory(_) => Some(path.clone()),
-            _ => None,
-        })
-        .for_each(|dir| {
-            let size = files
-                .iter()
-                .filter_map(|(path, entry)| {
-                    (path.starts_with(&dir)).then_some(match entry {
-                        DirectoryContent::File(file) => file.size,
-                        DirectoryContent::Directory(dir) => dir.size,
-                    })
-                })
-                .sum();
-
-            match file_tree.get_mut(&dir).unwrap() {
-                DirectoryContent::File(_) => panic!("Should not be a file"),
-                DirectoryContent::Directory(dir) => dir.size = size,
-            }
-        });
+    file_tree.calc_sizes();
 
-    file_tree
+    Ok(file_tree)
 }
 
 #[derive(Debug, Clone)]
@@ -128,8 +96,10 @@ struct Directory {
 
 #[derive(Debug)]
 enum ChangeDirectory {
-    Destination(String),
+    Root,
     Up,
+    Current,
+    Child(String),
 }
 
 impl FromStr for ChangeDirectory {
@@ -138,37 +108,68 @@ impl FromStr for ChangeDirectory {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(match s {
             ".." => ChangeDirectory::Up,
-            _ => ChangeDirectory::Destination(s.to_string()),
+            "." => ChangeDirectory::Current,
+            _ => ChangeDirectory::Child(s.to_string()),
         })
     }
 }
 
+fn get_segments(target: &str) -> anyhow::Result<Vec<ChangeDirectory>> {
+    let mut segments = vec![];
+
+    if target.starts_with('/') {
+        segments.push(ChangeDirectory::Root);
+    }
+
+    for segment in target.split('/').filter(|segment| !segment.is_empty()) {
+        segments.push(segment.parse()?);
+    }
+
+    if segments.is_empty() {
+        bail!("missing cd target");
+    }
+
+    Ok(segments)
+}
+
 #[derive(Debug, Clone)]
 enum DirectoryContent {
     File(File),
     Directory(Directory),
 }
 
+impl DirectoryContent {
+    fn name(&self) -> &str {
+        match self {
+            DirectoryContent::File(file) => &file.name,
+            DirectoryContent::Directory(dir) => &dir.name,
+        }
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            DirectoryContent::File(file) => file.size,
+            DirectoryContent::Directory(dir) => dir.size,
+        }
+    }
+}
+
 impl FromStr for DirectoryContent {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let input: Vec<&str> = s.split_whitespace().collect();
-
-        let input_type = input[0].parse::<usize>();
-
-        Ok(match input_type {
-            Ok(size) => DirectoryContent::File(File {
-                size,
-                name: input[1].to_string(),
+        Ok(match s.split_whitespace().collect::<Vec<&str>>()[..] {
+            ["dir", name] => DirectoryContent::Directory(Directory {
+                name: name.to_string(),
+                size: 0,
             }),
-            Err(_) => match input[0] {
-                "dir" => DirectoryContent::Directory(Directory {
-                    name: input[1].to_string(),
-                    size: 0,
-                }),
-                _ => panic!("Unknown content {:?}", input),
-            },
+            [size, name] => DirectoryContent::File(File {
+                size: size
+                    .parse()
+                    .map_err(|_| anyhow!("invalid file size {:?}", size))?,
+                name: name.to_string(),
+            }),
+            _ => bail!("unknown output {:?}", s),
         })
     }
 }
@@ -176,39 +177,48 @@ impl FromStr for DirectoryContent {
 #[derive(Debug)]
 enum Command {
     Output(DirectoryContent),
-    Cd(ChangeDirectory),
+    Cd(Vec<ChangeDirectory>),
     Ls,
 }
 
 impl Command {
-    fn execute(
-        &self,
-        file_tree: &mut BTreeMap<String, DirectoryContent>,
-        path: &mut PathBuf,
-    ) -> () {
+    fn execute(&self, file_tree: &mut Tree, cwd: &mut usize) -> anyhow::Result<()> {
         match self {
-            Command::Output(content) => match content {
-                DirectoryContent::File(file) => {
-                    let mut path = path.clone();
-                    path.push(file.name.clone());
-                    file_tree.insert(path.display().to_string(), content.clone());
-                }
-                DirectoryContent::Directory(dir) => {
-                    let mut path = path.clone();
-                    path.push(dir.name.clone());
-                    file_tree.insert(path.display().to_string(), content.clone());
-                }
-            },
-            Command::Cd(c) => match c {
-                ChangeDirectory::Destination(d) => {
-                    path.push(d);
-                }
-                ChangeDirectory::Up => {
-                    path.pop();
+            Command::Output(content) => {
+                file_tree.insert(*cwd, content.clone());
+            }
+            Command::Cd(segments) => {
+                let mut target = *cwd;
+
+                for segment in segments {
+                    target = match segment {
+                        ChangeDirectory::Root => tree::ROOT,
+                        ChangeDirectory::Current => target,
+                        ChangeDirectory::Up => file_tree
+                            .parent(target)
+                            .ok_or_else(|| anyhow!("/ has no parent directory"))?,
+                        ChangeDirectory::Child(name) => {
+                            let path = format!(
+                                "{}/{}",
+                                file_tree.path(target).trim_end_matches('/'),
+                                name
+                            );
+
+                            match file_tree.child(target, name) {
+                                Some(child) if file_tree.is_directory(child) => child,
+                                Some(_) => bail!("{} is a file", path),
+                                None => bail!("directory {} was never listed", path),
+                            }
+                        }
+                    };
                 }
-            },
-            Command::Ls => return,
+
+                *cwd = target;
+            }
+            Command::Ls => {}
         }
+
+        Ok(())
     }
 }
 
@@ -216,16 +226,11 @@ impl FromStr for Command {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let input: Vec<&str> = s.split_whitespace().collect();
-
-        if input[0] != "$" {
-            return Ok(Command::Output(s.parse().unwrap()));
-        };
-
-        Ok(match input[1] {
-            "cd" => Command::Cd(input[2].parse().unwrap()),
-            "ls" => Command::Ls,
-            _ => panic!("Unknown command {:?}", input[1]),
+        Ok(match s.split_whitespace().collect::<Vec<&str>>()[..] {
+            ["$", "cd", target] => Command::Cd(get_segments(target)?),
+            ["$", "ls"] => Command::Ls,
+            ["$", ..] => bail!("unknown command {:?}", s),
+            _ => Command::Output(s.parse()?),
         })
     }
 }
@@ -238,13 +243,122 @@ mod tests {
     fn example_01() {
         let input = read_file("example.txt").unwrap();
 
-        assert_eq!(part_01(&input), 95437);
+        assert_eq!(part_01(&input).unwrap(), 95437);
     }
 
     #[test]
     fn example_02() {
         let input = read_file("example.txt").unwrap();
 
-        assert_eq!(part_02(&input), 24933642);
+        assert_eq!(part_02(&input).unwrap(), 24933642);
+    }
+
+    fn transcript(lines: &str) -> Vec<String> {
+        lines.lines().map(|line| line.trim().to_string()).collect()
+    }
+
+    #[test]
+    fn test_sibling_prefixes() {
+        let input = transcript(
+            "$ cd /
+             $ ls
+             dir a
+             dir ab
+             $ cd a
+             $ ls
+             10 x
+             $ cd ..
+             $ cd ab
+             $ ls
+             20 y",
+        );
+
+        let file_tree = calc_directory_sizes(&get_commands(&input).unwrap()).unwrap();
+        let sizes: Vec<usize> = file_tree.directories().map(|dir| dir.size).collect();
+
+        assert_eq!(sizes, vec![30, 10, 20]);
+    }
+
+    #[test]
+    fn test_nested_sizes_counted_once() {
+        let input = transcript(
+            "$ cd /
+             $ ls
+             dir a
+             $ cd a
+             $ ls
+             dir b
+             $ ls
+             dir b
+             $ cd b
+             $ ls
+             dir c
+             5 x
+             $ cd c
+             $ ls
+             7 y",
+        );
+
+        assert_eq!(part_01(&input).unwrap(), 12 + 12 + 12 + 7);
+    }
+
+    #[test]
+    fn test_enough_free_space() {
+        let input = transcript(
+            "$ cd /
+             $ ls
+             10 a",
+        );
+
+        assert_eq!(part_02(&input).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_path_resolution() {
+        let input = transcript(
+            "$ cd /
+             $ ls
+             dir a
+             $ cd a
+             $ ls
+             dir b
+             $ cd ./b
+             $ ls
+             dir c
+             3 x
+             $ cd /a/b/c
+             $ ls
+             4 y
+             $ cd ../../..
+             $ cd a/b/../b/c
+             $ ls
+             5 z",
+        );
+
+        assert_eq!(part_01(&input).unwrap(), 12 * 3 + 9);
+    }
+
+    #[test]
+    fn test_invalid_transcripts() {
+        let error = |lines: &str| part_01(&transcript(lines)).unwrap_err().to_string();
+
+        assert_eq!(
+            error("$ cd /\n$ cd a"),
+            "Line 2: directory /a was never listed"
+        );
+        assert_eq!(
+            error("$ cd /\n$ ls\ndir a\n$ cd /a/b"),
+            "Line 4: directory /a/b was never listed"
+        );
+        assert_eq!(error("$ cd /\n$ ls\n10 a\n$ cd a"), "Line 4: /a is a file");
+        assert_eq!(
+            error("$ cd /\n$ cd .."),
+            "Line 2: / has no parent directory"
+        );
+        assert_eq!(
+            error("$ rm -rf /"),
+            "Line 1: unknown command \"$ rm -rf /\""
+        );
+        assert_eq!(error("x y"), "Line 1: invalid file size \"x\"");
     }
 }
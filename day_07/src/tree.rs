@@ -0,0 +1,106 @@
+use crate::{Directory, DirectoryContent};
+
+pub const ROOT: usize = 0;
+
+#[derive(Debug)]
+pub struct Node {
+    pub parent: Option<usize>,
+    pub children: Vec<usize>,
+    pub content: DirectoryContent,
+}
+
+#[derive(Debug)]
+pub struct Tree {
+    nodes: Vec<Node>,
+}
+
+impl Tree {
+    pub fn new() -> Self {
+        Tree {
+            nodes: vec![Node {
+                parent: None,
+                children: vec![],
+                content: DirectoryContent::Directory(Directory {
+                    name: "/".to_string(),
+                    size: 0,
+                }),
+            }],
+        }
+    }
+
+    pub fn parent(&self, node: usize) -> Option<usize> {
+        self.nodes[node].parent
+    }
+
+    pub fn child(&self, node: usize, name: &str) -> Option<usize> {
+        self.nodes[node]
+            .children
+            .iter()
+            .copied()
+            .find(|child| self.nodes[*child].content.name() == name)
+    }
+
+    pub fn insert(&mut self, parent: usize, content: DirectoryContent) -> usize {
+        if let Some(existing) = self.child(parent, content.name()) {
+            return existing;
+        }
+
+        let node = self.nodes.len();
+
+        self.nodes.push(Node {
+            parent: Some(parent),
+            children: vec![],
+            content,
+        });
+        self.nodes[parent].children.push(node);
+
+        node
+    }
+
+    pub fn calc_sizes(&mut self) {
+        for node in &mut self.nodes {
+            if let DirectoryContent::Directory(dir) = &mut node.content {
+                dir.size = 0;
+            }
+        }
+
+        for node in (0..self.nodes.len()).rev() {
+            let size = self.nodes[node].content.size();
+
+            if let Some(parent) = self.nodes[node].parent {
+                if let DirectoryContent::Directory(dir) = &mut self.nodes[parent].content {
+                    dir.size += size;
+                }
+            }
+        }
+    }
+
+    pub fn is_directory(&self, node: usize) -> bool {
+        matches!(self.nodes[node].content, DirectoryContent::Directory(_))
+    }
+
+    pub fn path(&self, node: usize) -> String {
+        let mut names = vec![];
+        let mut current = node;
+
+        while let Some(parent) = self.nodes[current].parent {
+            names.push(self.nodes[current].content.name());
+            current = parent;
+        }
+
+        names.reverse();
+
+        format!("/{}", names.join("/"))
+    }
+
+    pub fn size(&self, node: usize) -> usize {
+        self.nodes[node].content.size()
+    }
+
+    pub fn directories(&self) -> impl Iterator<Item = &Directory> {
+        self.nodes.iter().filter_map(|node| match &node.content {
+            DirectoryContent::Directory(dir) => Some(dir),
+            DirectoryContent::File(_) => None,
+        })
+    }
+}